@@ -0,0 +1,83 @@
+//! cgroup v2 resource accounting for benchmark runs on Linux.
+//!
+//! `sysinfo`'s `Process::memory()`/CPU polling in `sampling.rs` samples at a
+//! 50ms cadence — it can miss a peak between polls and never accounts for
+//! memory or CPU time spent in Ollama's child processes (e.g. a separate
+//! runner subprocess for the loaded model). Where the host exposes Ollama's
+//! cgroup v2 accounting files, this reads `memory.peak`/`memory.current` and
+//! `cpu.stat`'s `usage_usec` directly from the kernel for exact, reproducible
+//! totals instead.
+
+use std::path::PathBuf;
+
+/// A resolved cgroup v2 directory for a process, ready for repeated reads.
+pub struct CgroupHandle {
+    dir: PathBuf,
+}
+
+impl CgroupHandle {
+    /// Resolve `pid`'s cgroup v2 directory under `/sys/fs/cgroup`.
+    ///
+    /// Returns `None` on cgroup v1 hosts (`/proc/<pid>/cgroup` has one line
+    /// per controller rather than the single `0::<path>` line v2 uses), or if
+    /// the accounting files underneath aren't readable - callers should fall
+    /// back to the existing sysinfo-based path in that case.
+    #[cfg(target_os = "linux")]
+    pub fn resolve(pid: sysinfo::Pid) -> Option<Self> {
+        let cgroup_file = std::fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+        let mut lines = cgroup_file.lines().filter(|l| !l.is_empty());
+        let line = lines.next()?;
+        if lines.next().is_some() {
+            log::debug!("cgroup v1 host (multiple controller lines for PID {pid}), falling back to sysinfo");
+            return None;
+        }
+
+        let relative_path = line.strip_prefix("0::")?.trim_start_matches('/');
+        let dir = PathBuf::from("/sys/fs/cgroup").join(relative_path);
+
+        let handle = Self { dir };
+        // Probe readability up front so callers fall back to sysinfo once,
+        // instead of silently reporting zeroed-out accounting for the whole run.
+        if handle.read_memory_current().is_none() || handle.read_cpu_usage_usec().is_none() {
+            log::debug!("cgroup accounting files unreadable under {:?}, falling back to sysinfo", handle.dir);
+            return None;
+        }
+
+        Some(handle)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn resolve(_pid: sysinfo::Pid) -> Option<Self> {
+        None
+    }
+
+    /// Current memory usage (bytes), from `memory.current`.
+    pub fn read_memory_current(&self) -> Option<u64> {
+        self.read_u64_file("memory.current")
+    }
+
+    /// True peak memory usage (bytes) since the cgroup was created, including
+    /// child processes, from `memory.peak`. Requires a kernel new enough to
+    /// expose this file (6.x); absent on older hosts.
+    pub fn read_memory_peak(&self) -> Option<u64> {
+        self.read_u64_file("memory.peak")
+    }
+
+    /// Cumulative CPU time consumed (microseconds), from `cpu.stat`'s
+    /// `usage_usec` line. Snapshot this before and after a run and subtract
+    /// to get exact CPU time for that window.
+    pub fn read_cpu_usage_usec(&self) -> Option<u64> {
+        let stat = std::fs::read_to_string(self.dir.join("cpu.stat")).ok()?;
+        stat.lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))
+            .and_then(|v| v.trim().parse().ok())
+    }
+
+    fn read_u64_file(&self, name: &str) -> Option<u64> {
+        std::fs::read_to_string(self.dir.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
@@ -0,0 +1,465 @@
+//! Regression detection between two exported benchmark CSVs.
+//!
+//! Re-parses the CSVs [`super::export::export_to_csv`] writes back into
+//! per-group metric samples, joins the two runs on `(category, model,
+//! prompt)`, and reports whether each metric moved enough - and
+//! significantly enough - to call it a real change rather than measurement
+//! noise, so this can be wired into a CI gate instead of requiring someone
+//! to diff two CSVs by hand.
+
+use super::stats::{self, MetricStats};
+use csv::ReaderBuilder;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One joined `(category, model, prompt)` group's metric samples from a
+/// single run, before comparison.
+struct ParsedRow {
+    category: String,
+    model: String,
+    prompt: String,
+    tokens_per_second: f64,
+    first_token_ms: f64,
+    total_time_ms: f64,
+    peak_memory_mb: f64,
+}
+
+/// Metrics compared between runs, and whether a higher value is better (used
+/// to decide "improved" vs "regressed" direction once a change is flagged).
+const COMPARED_METRICS: [(&str, fn(&ParsedRow) -> f64, bool); 4] = [
+    ("tokens_per_sec", |r| r.tokens_per_second, true),
+    ("first_token_ms", |r| r.first_token_ms, false),
+    ("total_time_ms", |r| r.total_time_ms, false),
+    ("peak_memory_mb", |r| r.peak_memory_mb, false),
+];
+
+/// Parse a benchmark CSV (as written by [`super::export::export_to_csv`])
+/// back into the handful of columns this module needs, by header name
+/// rather than position - so it isn't broken by unrelated columns being
+/// added to `CsvMetricRow` later.
+fn parse_benchmark_csv(path: &Path) -> Result<Vec<ParsedRow>, String> {
+    let mut reader = ReaderBuilder::new()
+        .from_path(path)
+        .map_err(|e| format!("Failed to open benchmark CSV {path:?}: {e}"))?;
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read headers from {path:?}: {e}"))?
+        .clone();
+
+    let column = |name: &str| -> Result<usize, String> {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| format!("Column '{name}' missing from {path:?}"))
+    };
+    let category_col = column("category")?;
+    let model_col = column("model")?;
+    let prompt_col = column("prompt")?;
+    let tokens_per_sec_col = column("tokens_per_sec")?;
+    let first_token_ms_col = column("first_token_ms")?;
+    let total_time_ms_col = column("total_time_ms")?;
+    let peak_memory_mb_col = column("memory_peak_mb")?;
+
+    let parse_field = |record: &csv::StringRecord, idx: usize, field: &str| -> Result<f64, String> {
+        record
+            .get(idx)
+            .ok_or_else(|| format!("Missing '{field}' value in {path:?}"))?
+            .parse::<f64>()
+            .map_err(|e| format!("Malformed '{field}' value in {path:?}: {e}"))
+    };
+
+    let mut rows = Vec::new();
+    for result in reader.records() {
+        let record = result.map_err(|e| format!("Failed to read row from {path:?}: {e}"))?;
+        rows.push(ParsedRow {
+            category: record.get(category_col).unwrap_or_default().to_string(),
+            model: record.get(model_col).unwrap_or_default().to_string(),
+            prompt: record.get(prompt_col).unwrap_or_default().to_string(),
+            tokens_per_second: parse_field(&record, tokens_per_sec_col, "tokens_per_sec")?,
+            first_token_ms: parse_field(&record, first_token_ms_col, "first_token_ms")?,
+            total_time_ms: parse_field(&record, total_time_ms_col, "total_time_ms")?,
+            peak_memory_mb: parse_field(&record, peak_memory_mb_col, "memory_peak_mb")?,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Group parsed rows by `(category, model, prompt)`, as a `BTreeMap` for a
+/// deterministic report order.
+fn group_rows(rows: &[ParsedRow]) -> BTreeMap<(String, String, String), Vec<&ParsedRow>> {
+    let mut groups: BTreeMap<(String, String, String), Vec<&ParsedRow>> = BTreeMap::new();
+    for row in rows {
+        groups
+            .entry((row.category.clone(), row.model.clone(), row.prompt.clone()))
+            .or_default()
+            .push(row);
+    }
+    groups
+}
+
+/// Verdict for one metric within one joined group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComparisonVerdict {
+    Improved,
+    Regressed,
+    Pass,
+}
+
+/// Comparison of one metric between the baseline and candidate run, within
+/// one `(category, model, prompt)` group.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricComparison {
+    pub metric: String,
+    pub baseline_mean: f64,
+    pub candidate_mean: f64,
+    /// `(candidate - baseline) / baseline * 100`. Positive means the
+    /// candidate's raw value is higher, independent of whether higher is
+    /// good or bad for this metric.
+    pub percent_change: f64,
+    /// `true` only if the baseline and candidate's 95% bootstrap confidence
+    /// intervals don't overlap - a crude significance gate against calling
+    /// sampling noise a regression.
+    pub significant: bool,
+    pub verdict: ComparisonVerdict,
+}
+
+/// All metric comparisons for one joined `(category, model, prompt)` group.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupComparison {
+    pub category: String,
+    pub model: String,
+    pub prompt: String,
+    pub metrics: Vec<MetricComparison>,
+}
+
+/// Full comparison between a baseline and candidate benchmark CSV.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonResult {
+    pub groups: Vec<GroupComparison>,
+    /// `true` if any metric in any group was flagged [`ComparisonVerdict::Regressed`].
+    pub regressed: bool,
+}
+
+/// Two confidence intervals don't overlap - the crude significance gate: if
+/// either run's CI is unavailable (too few samples), the change can't be
+/// called significant either way.
+fn cis_dont_overlap(baseline: &MetricStats, candidate: &MetricStats) -> bool {
+    match (baseline.ci_95, candidate.ci_95) {
+        (Some((b_lo, b_hi)), Some((c_lo, c_hi))) => c_hi < b_lo || b_hi < c_lo,
+        _ => false,
+    }
+}
+
+/// Compare two exported benchmark CSVs and decide, per `(category, model,
+/// prompt)` group and per metric, whether the candidate run improved,
+/// regressed, or stayed within noise of the baseline.
+///
+/// A metric is only flagged improved/regressed if its percent change
+/// exceeds `regression_threshold_percent` *and* the two runs' bootstrap
+/// confidence intervals don't overlap; otherwise it's reported as `Pass`
+/// even if the raw means differ, since that difference isn't distinguishable
+/// from sampling noise.
+pub fn compare_benchmark_csvs(
+    baseline_path: &Path,
+    candidate_path: &Path,
+    regression_threshold_percent: f64,
+) -> Result<ComparisonResult, String> {
+    let baseline_rows = parse_benchmark_csv(baseline_path)?;
+    let candidate_rows = parse_benchmark_csv(candidate_path)?;
+
+    let baseline_groups = group_rows(&baseline_rows);
+    let candidate_groups = group_rows(&candidate_rows);
+
+    let mut groups = Vec::new();
+    let mut regressed = false;
+
+    for (key, baseline_group) in &baseline_groups {
+        let Some(candidate_group) = candidate_groups.get(key) else {
+            continue;
+        };
+
+        let (category, model, prompt) = key.clone();
+        let mut metrics = Vec::with_capacity(COMPARED_METRICS.len());
+
+        for (metric_name, extractor, higher_is_better) in COMPARED_METRICS {
+            let baseline_samples: Vec<f64> = baseline_group.iter().map(|r| extractor(r)).collect();
+            let candidate_samples: Vec<f64> = candidate_group.iter().map(|r| extractor(r)).collect();
+
+            let baseline_stats = stats::summarize(&baseline_samples);
+            let candidate_stats = stats::summarize(&candidate_samples);
+
+            let percent_change = if baseline_stats.mean == 0.0 {
+                0.0
+            } else {
+                (candidate_stats.mean - baseline_stats.mean) / baseline_stats.mean * 100.0
+            };
+
+            let significant = cis_dont_overlap(&baseline_stats, &candidate_stats);
+            let exceeds_threshold = percent_change.abs() >= regression_threshold_percent;
+
+            let verdict = if significant && exceeds_threshold {
+                // The candidate moved further in the "good" direction.
+                let improved = if higher_is_better { percent_change > 0.0 } else { percent_change < 0.0 };
+                if improved {
+                    ComparisonVerdict::Improved
+                } else {
+                    ComparisonVerdict::Regressed
+                }
+            } else {
+                ComparisonVerdict::Pass
+            };
+
+            if verdict == ComparisonVerdict::Regressed {
+                regressed = true;
+            }
+
+            metrics.push(MetricComparison {
+                metric: metric_name.to_string(),
+                baseline_mean: baseline_stats.mean,
+                candidate_mean: candidate_stats.mean,
+                percent_change,
+                significant,
+                verdict,
+            });
+        }
+
+        groups.push(GroupComparison { category, model, prompt, metrics });
+    }
+
+    Ok(ComparisonResult { groups, regressed })
+}
+
+/// One CSV row in the comparison report: one metric within one joined group.
+#[derive(Debug, Serialize)]
+struct ComparisonCsvRow {
+    category: String,
+    model: String,
+    prompt: String,
+    metric: String,
+    baseline_mean: f64,
+    candidate_mean: f64,
+    percent_change: f64,
+    significant: bool,
+    verdict: ComparisonVerdict,
+}
+
+/// Write `result` as a flat CSV: one row per metric per joined group.
+pub fn export_comparison_csv(result: &ComparisonResult, output_path: &Path) -> Result<PathBuf, String> {
+    let mut wtr = csv::Writer::from_path(output_path)
+        .map_err(|e| format!("Failed to create comparison CSV {output_path:?}: {e}"))?;
+
+    for group in &result.groups {
+        for metric in &group.metrics {
+            wtr.serialize(ComparisonCsvRow {
+                category: group.category.clone(),
+                model: group.model.clone(),
+                prompt: group.prompt.clone(),
+                metric: metric.metric.clone(),
+                baseline_mean: metric.baseline_mean,
+                candidate_mean: metric.candidate_mean,
+                percent_change: metric.percent_change,
+                significant: metric.significant,
+                verdict: metric.verdict,
+            })
+            .map_err(|e| format!("Failed to serialize comparison row: {e}"))?;
+        }
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush comparison CSV writer: {e}"))?;
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Render `result` as a Markdown table suitable for pasting into a PR
+/// description or CI summary.
+pub fn render_comparison_markdown(result: &ComparisonResult) -> String {
+    let mut out = String::new();
+    out.push_str("| Category | Model | Prompt | Metric | Baseline | Candidate | Change | Significant | Verdict |\n");
+    out.push_str("|---|---|---|---|---|---|---|---|---|\n");
+
+    for group in &result.groups {
+        for metric in &group.metrics {
+            let verdict_label = match metric.verdict {
+                ComparisonVerdict::Improved => "✅ improved",
+                ComparisonVerdict::Regressed => "❌ regressed",
+                ComparisonVerdict::Pass => "pass",
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {:.2} | {:.2} | {:+.1}% | {} | {} |\n",
+                group.category,
+                group.model,
+                group.prompt,
+                metric.metric,
+                metric.baseline_mean,
+                metric.candidate_mean,
+                metric.percent_change,
+                metric.significant,
+                verdict_label,
+            ));
+        }
+    }
+
+    out.push_str(&format!(
+        "\n**Overall: {}**\n",
+        if result.regressed { "❌ REGRESSED" } else { "✅ pass" }
+    ));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn write_csv(rows: &[(&str, &str, &str, f64, f64, f64, f64)]) -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        let mut wtr = csv::Writer::from_path(file.path()).unwrap();
+        wtr.write_record([
+            "category",
+            "model",
+            "prompt",
+            "tokens_per_sec",
+            "first_token_ms",
+            "total_time_ms",
+            "memory_peak_mb",
+        ])
+        .unwrap();
+        for (category, model, prompt, tps, ftm, ttm, mem) in rows {
+            wtr.write_record([
+                category.to_string(),
+                model.to_string(),
+                prompt.to_string(),
+                tps.to_string(),
+                ftm.to_string(),
+                ttm.to_string(),
+                mem.to_string(),
+            ])
+            .unwrap();
+        }
+        wtr.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_compare_flags_improvement_when_significant() {
+        // Tight, non-overlapping clusters: candidate throughput is clearly higher.
+        let baseline = write_csv(&[
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.1, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 9.9, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+        ]);
+        let candidate = write_csv(&[
+            ("short", "model-a", "hi", 20.0, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 20.1, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 19.9, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 20.0, 100.0, 1000.0, 500.0),
+        ]);
+
+        let result = compare_benchmark_csvs(baseline.path(), candidate.path(), 5.0).unwrap();
+
+        assert_eq!(result.groups.len(), 1);
+        let tps = result.groups[0]
+            .metrics
+            .iter()
+            .find(|m| m.metric == "tokens_per_sec")
+            .unwrap();
+        assert_eq!(tps.verdict, ComparisonVerdict::Improved);
+        assert!(tps.significant);
+        assert!(!result.regressed);
+    }
+
+    #[test]
+    fn test_compare_flags_regression_on_slower_candidate() {
+        let baseline = write_csv(&[
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.1, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 9.9, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+        ]);
+        // first_token_ms jumps from ~100ms (baseline) to ~200ms (candidate) - worse, significant.
+        let candidate = write_csv(&[
+            ("short", "model-a", "hi", 10.0, 200.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.1, 210.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 9.9, 190.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.0, 205.0, 1000.0, 500.0),
+        ]);
+
+        let result = compare_benchmark_csvs(baseline.path(), candidate.path(), 5.0).unwrap();
+
+        let first_token = result.groups[0]
+            .metrics
+            .iter()
+            .find(|m| m.metric == "first_token_ms")
+            .unwrap();
+        assert_eq!(first_token.verdict, ComparisonVerdict::Regressed);
+        assert!(result.regressed);
+    }
+
+    #[test]
+    fn test_compare_below_threshold_is_pass() {
+        let baseline = write_csv(&[
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.1, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 9.9, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+        ]);
+        // Only ~1% faster - below the 5% threshold even though it's "better".
+        let candidate = write_csv(&[
+            ("short", "model-a", "hi", 10.1, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.2, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0),
+            ("short", "model-a", "hi", 10.1, 100.0, 1000.0, 500.0),
+        ]);
+
+        let result = compare_benchmark_csvs(baseline.path(), candidate.path(), 5.0).unwrap();
+
+        let tps = result.groups[0]
+            .metrics
+            .iter()
+            .find(|m| m.metric == "tokens_per_sec")
+            .unwrap();
+        assert_eq!(tps.verdict, ComparisonVerdict::Pass);
+        assert!(!result.regressed);
+    }
+
+    #[test]
+    fn test_compare_only_joins_matching_groups() {
+        let baseline = write_csv(&[("short", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0)]);
+        let candidate = write_csv(&[("medium", "model-a", "hi", 10.0, 100.0, 1000.0, 500.0)]);
+
+        let result = compare_benchmark_csvs(baseline.path(), candidate.path(), 5.0).unwrap();
+
+        assert!(result.groups.is_empty(), "Disjoint categories shouldn't join into any group");
+    }
+
+    #[test]
+    fn test_render_comparison_markdown_contains_overall_verdict() {
+        let result = ComparisonResult {
+            groups: vec![GroupComparison {
+                category: "short".to_string(),
+                model: "model-a".to_string(),
+                prompt: "hi".to_string(),
+                metrics: vec![MetricComparison {
+                    metric: "tokens_per_sec".to_string(),
+                    baseline_mean: 10.0,
+                    candidate_mean: 20.0,
+                    percent_change: 100.0,
+                    significant: true,
+                    verdict: ComparisonVerdict::Improved,
+                }],
+            }],
+            regressed: false,
+        };
+
+        let markdown = render_comparison_markdown(&result);
+        assert!(markdown.contains("tokens_per_sec"));
+        assert!(markdown.contains("pass"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Constrained-hardware benchmark mode.
+//!
+//! Runs the Ollama inference process inside a transient cgroup v2 with an
+//! enforced memory/CPU envelope, so a benchmark can answer "how would this
+//! model behave on a weaker machine than the one I'm testing on" instead of
+//! only reporting unconstrained performance.
+
+use super::metrics::BenchmarkResults;
+use super::process::warmup_and_find_process;
+use super::runner::{run_benchmark_suite, BenchmarkProgress};
+use crate::commands::ollama::OllamaConfig;
+use serde::{Deserialize, Serialize};
+
+/// Resource envelope to impose on the inference process for the duration of
+/// a constrained benchmark run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ResourceEnvelope {
+    /// Memory cap (bytes), written to `memory.max`.
+    pub memory_max_bytes: u64,
+    /// CPU quota (microseconds) allowed per `cpu_period_usec`, the first
+    /// field of `cpu.max`. E.g. `200_000` with a 100ms period caps the
+    /// process at 2 cores.
+    pub cpu_quota_usec: u64,
+    /// CPU accounting period (microseconds), the second field of `cpu.max`.
+    /// `100_000` (100ms) is the conventional value `cpu_quota_usec` is
+    /// expressed against.
+    pub cpu_period_usec: u64,
+}
+
+/// Outcome of a constrained benchmark run: either full results under the cap,
+/// or a report that the inference process was OOM-killed by the kernel
+/// before completing. An OOM-kill under a deliberately tight memory cap is a
+/// meaningful benchmark result ("this model doesn't fit in 8 GB"), not a
+/// crash, so it's represented as a variant here rather than surfaced as an
+/// `Err`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConstrainedBenchmarkOutcome {
+    Completed(BenchmarkResults),
+    OomKilled,
+}
+
+/// Results of a benchmark run under an enforced resource envelope, alongside
+/// the envelope itself so callers can label "at 8 GB / 2 cores" vs
+/// unconstrained results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstrainedBenchmarkResults {
+    pub envelope: ResourceEnvelope,
+    pub outcome: ConstrainedBenchmarkOutcome,
+}
+
+/// Run [`run_benchmark_suite`] with the inference process confined to
+/// `envelope`'s memory/CPU cap.
+///
+/// Requires write access to `/sys/fs/cgroup` (root, or cgroup delegation to
+/// this process) - returns a clear error rather than silently running
+/// unconstrained if the app lacks it. Linux-only: cgroup v2 has no analog on
+/// macOS/Windows.
+#[cfg(target_os = "linux")]
+pub async fn run_constrained_benchmark(
+    model: String,
+    envelope: ResourceEnvelope,
+    iterations: usize,
+    warmup_iterations: usize,
+    client: &reqwest::Client,
+    config: &OllamaConfig,
+    progress_callback: impl Fn(BenchmarkProgress),
+) -> Result<ConstrainedBenchmarkResults, String> {
+    let pid = warmup_and_find_process(&model, client, config).await?;
+
+    // `_scoped_cgroup` is never read again, but must stay alive (and therefore
+    // bound to a name, not `_`) until the suite finishes: its `Drop` impl is
+    // the teardown guard that restores `pid` to its original cgroup, and runs
+    // even if `run_benchmark_suite` panics or this future is cancelled.
+    let _scoped_cgroup = linux_impl::ScopedCgroup::create(pid, &envelope)?;
+
+    let suite_result = run_benchmark_suite(
+        model,
+        iterations,
+        warmup_iterations,
+        false,
+        client,
+        config,
+        None,
+        progress_callback,
+        |_tokens_per_sec| {},
+    )
+    .await;
+
+    let outcome = match suite_result {
+        Ok(results) => ConstrainedBenchmarkOutcome::Completed(results),
+        Err(e) if !linux_impl::process_is_alive(pid) => {
+            log::warn!(
+                "Inference process (PID {pid}) disappeared under the resource envelope - \
+                 treating as an OOM-kill rather than a benchmark failure: {e}"
+            );
+            ConstrainedBenchmarkOutcome::OomKilled
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(ConstrainedBenchmarkResults { envelope, outcome })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn run_constrained_benchmark(
+    _model: String,
+    _envelope: ResourceEnvelope,
+    _iterations: usize,
+    _warmup_iterations: usize,
+    _client: &reqwest::Client,
+    _config: &OllamaConfig,
+    _progress_callback: impl Fn(BenchmarkProgress),
+) -> Result<ConstrainedBenchmarkResults, String> {
+    Err("Constrained-hardware benchmarking requires Linux cgroup v2 and isn't available on this platform".to_string())
+}
+
+#[cfg(target_os = "linux")]
+mod linux_impl {
+    use super::ResourceEnvelope;
+    use std::path::{Path, PathBuf};
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+    const SCOPE_NAME_PREFIX: &str = "smolpc-benchmark-";
+
+    /// Scoped cgroup created for one constrained run. Restores the process to
+    /// its original cgroup and removes the transient one on drop, so a panic
+    /// or a cancelled future still cleans up instead of leaving Ollama
+    /// permanently capped.
+    pub(super) struct ScopedCgroup {
+        dir: PathBuf,
+        pid: sysinfo::Pid,
+        original_cgroup_dir: PathBuf,
+    }
+
+    impl ScopedCgroup {
+        /// Create a transient cgroup under `/sys/fs/cgroup`, apply `envelope`,
+        /// and move `pid` into it. Captures `pid`'s current cgroup path first
+        /// so teardown can restore it.
+        pub(super) fn create(pid: sysinfo::Pid, envelope: &ResourceEnvelope) -> Result<Self, String> {
+            let original_cgroup_dir = read_process_cgroup_dir(pid)?;
+
+            let dir = Path::new(CGROUP_ROOT).join(format!("{SCOPE_NAME_PREFIX}{pid}"));
+            std::fs::create_dir(&dir).map_err(|e| {
+                format!(
+                    "Failed to create transient cgroup at {dir:?} - the app needs write access \
+                     to {CGROUP_ROOT} (run as root, or delegate a cgroup subtree to this process): {e}"
+                )
+            })?;
+
+            let scoped = Self { dir, pid, original_cgroup_dir };
+            scoped.write_control("memory.max", &envelope.memory_max_bytes.to_string())?;
+            scoped.write_control("cpu.max", &format!("{} {}", envelope.cpu_quota_usec, envelope.cpu_period_usec))?;
+            scoped.write_control("cgroup.procs", &pid.to_string())?;
+
+            Ok(scoped)
+        }
+
+        fn write_control(&self, file: &str, value: &str) -> Result<(), String> {
+            std::fs::write(self.dir.join(file), value)
+                .map_err(|e| format!("Failed to write {file} under {:?}: {e}", self.dir))
+        }
+    }
+
+    impl Drop for ScopedCgroup {
+        fn drop(&mut self) {
+            if let Err(e) = std::fs::write(self.original_cgroup_dir.join("cgroup.procs"), self.pid.to_string()) {
+                log::error!(
+                    "Failed to restore PID {} to its original cgroup {:?} - it remains capped \
+                     until manually moved: {e}",
+                    self.pid,
+                    self.original_cgroup_dir
+                );
+            }
+            if let Err(e) = std::fs::remove_dir(&self.dir) {
+                log::warn!("Failed to remove transient cgroup {:?}: {e}", self.dir);
+            }
+        }
+    }
+
+    /// Read `pid`'s current cgroup v2 directory from `/proc/<pid>/cgroup`
+    /// (the `0::<path>` line).
+    fn read_process_cgroup_dir(pid: sysinfo::Pid) -> Result<PathBuf, String> {
+        let cgroup_file = std::fs::read_to_string(format!("/proc/{pid}/cgroup"))
+            .map_err(|e| format!("Failed to read /proc/{pid}/cgroup: {e}"))?;
+        let relative_path = cgroup_file
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .ok_or_else(|| format!("PID {pid} is not on a cgroup v2 hierarchy (no 0:: line)"))?;
+
+        Ok(Path::new(CGROUP_ROOT).join(relative_path.trim_start_matches('/')))
+    }
+
+    /// Check whether the process still exists, to distinguish a clean exit
+    /// from the OOM killer taking it out mid-run.
+    pub(super) fn process_is_alive(pid: sysinfo::Pid) -> bool {
+        let mut sys = sysinfo::System::new_all();
+        sys.refresh_all();
+        sys.process(pid).is_some()
+    }
+}
@@ -0,0 +1,72 @@
+//! Exact per-process CPU-time measurement via the POSIX process CPU clock,
+//! as an alternative to `sampling.rs`'s periodic %-sampling.
+//!
+//! Periodic sampling can miss a CPU burst that happens entirely between two
+//! samples, and is noisy for short prompts where there are only a handful
+//! of samples to average. `cpu_time::ProcessTime` wraps the OS's own
+//! per-process CPU-time accounting (`clock_gettime(CLOCK_PROCESS_CPUTIME_ID)`
+//! on Linux), so diffing two snapshots gives the exact CPU-seconds consumed
+//! over an interval with no sampling gap. It only measures the *calling*
+//! process, though, so this only applies to the Tauri process's own CPU
+//! usage - not Ollama, which runs as a separate process.
+
+use cpu_time::ProcessTime;
+use std::time::Duration;
+
+/// A snapshot of this process's CPU time, to be diffed against a later
+/// snapshot via [`elapsed_cpu_percent`].
+pub struct CpuClockSnapshot(ProcessTime);
+
+impl CpuClockSnapshot {
+    /// Capture now. `None` if the platform doesn't support the POSIX
+    /// process CPU clock.
+    pub fn capture() -> Option<Self> {
+        ProcessTime::try_now().ok().map(Self)
+    }
+}
+
+/// Exact average CPU utilization (%) of this process between `start` and
+/// `end`, as consumed CPU-seconds over `wall_clock_elapsed` wall-clock
+/// seconds. `None` if `wall_clock_elapsed` is zero (can't divide by it).
+pub fn elapsed_cpu_percent(
+    start: &CpuClockSnapshot,
+    end: &CpuClockSnapshot,
+    wall_clock_elapsed: Duration,
+) -> Option<f64> {
+    let wall_secs = wall_clock_elapsed.as_secs_f64();
+    if wall_secs <= 0.0 {
+        return None;
+    }
+
+    let cpu_elapsed = end.0 - start.0;
+    Some((cpu_elapsed.as_secs_f64() / wall_secs) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elapsed_cpu_percent_zero_wall_clock_is_none() {
+        let start = CpuClockSnapshot::capture().expect("process CPU clock should be available");
+        let end = CpuClockSnapshot::capture().expect("process CPU clock should be available");
+
+        assert_eq!(elapsed_cpu_percent(&start, &end, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_elapsed_cpu_percent_is_non_negative() {
+        let start = CpuClockSnapshot::capture().expect("process CPU clock should be available");
+        // Burn a little CPU so the snapshots aren't identical.
+        let mut acc: u64 = 0;
+        for i in 0..1_000_000u64 {
+            acc = acc.wrapping_add(i);
+        }
+        std::hint::black_box(acc);
+        let end = CpuClockSnapshot::capture().expect("process CPU clock should be available");
+
+        let percent = elapsed_cpu_percent(&start, &end, Duration::from_millis(10))
+            .expect("wall clock is non-zero");
+        assert!(percent >= 0.0);
+    }
+}
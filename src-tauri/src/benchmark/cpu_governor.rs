@@ -0,0 +1,186 @@
+//! CPU governor / turbo-boost recording and pinning for reproducible
+//! benchmarks, via Linux cpufreq sysfs.
+//!
+//! Frequency scaling and turbo boost make benchmark numbers swing between
+//! runs for reasons that have nothing to do with the model being tested.
+//! This records the active governor/boost state in every result so readers
+//! can interpret variance, and optionally pins both to a known state for the
+//! duration of a run via [`StabilizeGuard`], which restores the prior values
+//! on drop.
+
+use std::path::{Path, PathBuf};
+
+const CPUFREQ_CPU0_DIR: &str = "/sys/devices/system/cpu/cpu0/cpufreq";
+const BOOST_FILE: &str = "/sys/devices/system/cpu/cpufreq/boost";
+
+/// Fraction below base frequency that counts as thermal throttling. Chosen
+/// loosely above typical idle dips/measurement noise so only a sustained,
+/// substantial drop trips it.
+const THROTTLE_FREQUENCY_RATIO: f64 = 0.85;
+
+/// CPU frequency-scaling state captured once per benchmark run, from `cpu0`
+/// (scaling governor/boost are almost always uniform across cores, and
+/// per-core drift is exactly what `thermal_throttling_suspected` exists to
+/// catch instead).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CpuFrequencyState {
+    /// Active scaling governor (e.g. "performance", "powersave",
+    /// "ondemand"), from `scaling_governor`. `None` if unreadable.
+    pub scaling_governor: Option<String>,
+    /// Whether turbo boost is enabled, from `cpufreq/boost`. `None` if the
+    /// driver doesn't expose a boost toggle (common on non-intel_pstate
+    /// hosts).
+    pub boost_enabled: Option<bool>,
+    /// Base (non-turbo) frequency in kHz: `cpuinfo_base_freq` if the driver
+    /// exposes it, else `scaling_max_freq` as the closest available figure.
+    pub base_frequency_khz: Option<u64>,
+    /// True when none of the cpufreq sysfs nodes above were readable on this
+    /// host (non-Linux, or no intel_pstate/acpi-cpufreq driver) - recorded
+    /// explicitly so a reader doesn't mistake missing data for "boost is
+    /// known to be off".
+    pub unsupported: bool,
+}
+
+impl CpuFrequencyState {
+    /// Read the current state from cpufreq sysfs. Linux-only - always
+    /// `unsupported` elsewhere.
+    #[cfg(target_os = "linux")]
+    pub fn detect() -> Self {
+        let scaling_governor = read_string(&format!("{CPUFREQ_CPU0_DIR}/scaling_governor"));
+        let boost_enabled = read_string(BOOST_FILE).and_then(|v| match v.as_str() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        });
+        let base_frequency_khz = read_u64(&format!("{CPUFREQ_CPU0_DIR}/cpuinfo_base_freq"))
+            .or_else(|| read_u64(&format!("{CPUFREQ_CPU0_DIR}/scaling_max_freq")));
+
+        let unsupported = scaling_governor.is_none() && boost_enabled.is_none() && base_frequency_khz.is_none();
+
+        Self { scaling_governor, boost_enabled, base_frequency_khz, unsupported }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn detect() -> Self {
+        Self { scaling_governor: None, boost_enabled: None, base_frequency_khz: None, unsupported: true }
+    }
+
+    /// `cpu0`'s current scaling frequency (kHz), for periodic polling during
+    /// a run to detect thermal throttling. `None` if unreadable.
+    #[cfg(target_os = "linux")]
+    pub fn read_current_frequency_khz() -> Option<u64> {
+        read_u64(&format!("{CPUFREQ_CPU0_DIR}/scaling_cur_freq"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read_current_frequency_khz() -> Option<u64> {
+        None
+    }
+}
+
+/// Whether `current_khz` is substantially below `base_khz`, suggesting the
+/// CPU has throttled down from thermal pressure mid-run.
+pub fn is_throttled(current_khz: u64, base_khz: u64) -> bool {
+    base_khz > 0 && (current_khz as f64) < (base_khz as f64) * THROTTLE_FREQUENCY_RATIO
+}
+
+/// Teardown guard for [`StabilizeGuard::stabilize`]: restores every governor
+/// and the boost flag to the values captured at creation time on drop, so a
+/// panic or cancelled future still leaves the host in its original state
+/// instead of pinned to "performance" forever.
+pub struct StabilizeGuard {
+    /// `(scaling_governor path, original value)` for every CPU core found.
+    original_governors: Vec<(PathBuf, String)>,
+    original_boost: Option<String>,
+}
+
+impl StabilizeGuard {
+    /// Set the performance governor on every CPU core and enable boost,
+    /// capturing the prior values to restore on drop.
+    ///
+    /// Returns `None` (no-op) rather than an error if the sysfs nodes aren't
+    /// writable (not root, or no delegation) - callers should run
+    /// unstabilized instead of failing the whole benchmark over it.
+    #[cfg(target_os = "linux")]
+    pub fn stabilize() -> Option<Self> {
+        let governor_paths = scaling_governor_paths();
+        if governor_paths.is_empty() {
+            return None;
+        }
+
+        // Built up front so an early return drops it, restoring the cores
+        // already switched to "performance".
+        let mut guard = Self {
+            original_governors: Vec::with_capacity(governor_paths.len()),
+            original_boost: None,
+        };
+        for path in governor_paths {
+            let original = std::fs::read_to_string(&path).ok()?.trim().to_string();
+            std::fs::write(&path, "performance").ok()?;
+            guard.original_governors.push((path, original));
+        }
+
+        guard.original_boost = read_string(BOOST_FILE);
+        if guard.original_boost.is_some() && std::fs::write(BOOST_FILE, "1").is_err() {
+            log::warn!("Failed to enable boost at {BOOST_FILE} while stabilizing CPU frequency for a benchmark run");
+        }
+
+        Some(guard)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn stabilize() -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for StabilizeGuard {
+    fn drop(&mut self) {
+        for (path, original) in &self.original_governors {
+            if let Err(e) = std::fs::write(path, original) {
+                log::error!("Failed to restore CPU governor at {path:?} to '{original}': {e}");
+            }
+        }
+        if let Some(original) = &self.original_boost {
+            if let Err(e) = std::fs::write(BOOST_FILE, original) {
+                log::error!("Failed to restore boost at {BOOST_FILE} to '{original}': {e}");
+            }
+        }
+    }
+}
+
+/// `scaling_governor` paths for every `cpuN/cpufreq` directory found under
+/// `/sys/devices/system/cpu`.
+#[cfg(target_os = "linux")]
+fn scaling_governor_paths() -> Vec<PathBuf> {
+    let cpu_root = Path::new("/sys/devices/system/cpu");
+    let Ok(entries) = std::fs::read_dir(cpu_root) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            let digits = name.strip_prefix("cpu")?;
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let governor_path = entry.path().join("cpufreq").join("scaling_governor");
+            governor_path.exists().then_some(governor_path)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_string(path: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn read_u64(path: &str) -> Option<u64> {
+    read_string(path)?.parse().ok()
+}
@@ -1,6 +1,10 @@
-use super::metrics::{BenchmarkMetrics, BenchmarkResults};
+use super::memory_breakdown::MemoryBreakdown;
+use super::metrics::{BenchmarkMetrics, BenchmarkResults, LatencyPercentiles};
+use super::stats::{self, MetricStats};
+use super::sustained_load::SustainedLoadResult;
 use csv::Writer;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -20,23 +24,61 @@ struct CsvMetricRow {
     total_time_ms: String,
     tokens_per_sec: String,
     avg_token_ms: String,
+    final_token_ewma_tokens_per_sec: String,
     timing_source: String,
     memory_before_mb: String,
     memory_peak_mb: String,
+    // Structured breakdown (see `memory_breakdown.rs`) - virtual/private/
+    // shared/file-backed are `None` off Linux or where smaps_rollup isn't readable.
+    memory_virtual_mb: String,
+    memory_private_mb: String,
+    memory_shared_mb: String,
+    memory_file_backed_mb: String,
     // Comprehensive CPU metrics for Ollama vs llama.cpp comparison
     cpu_ollama_percent: String,
     cpu_tauri_percent: String,
     cpu_system_percent: String,
     cpu_total_percent: String,
+    cpu_tauri_measurement: String,
     response_tokens: usize,
     cpu_model: String,
     gpu_name: String,
     avx2_supported: bool,
     npu_detected: bool,
     hardware_detection_failed: bool,
+    cpu_capability_score: String,
+    memory_capability_score: String,
+    scaling_governor: String,
+    boost_enabled: String,
+    cpu_base_frequency_khz: String,
+    cpu_freq_unsupported: bool,
+    gpu_utilization_percent: String,
+    gpu_vram_used_mb: String,
+    gpu_power_watts: String,
+    gpu_temperature_c: String,
+    ane_power_watts: String,
+    ane_residency_percent: String,
+    system_memory_total_mb: String,
+    system_memory_avg_free_mb: String,
+    system_memory_min_free_mb: String,
+    system_memory_avg_used_mb: String,
+    system_swap_avg_used_mb: String,
+    system_swap_peak_used_mb: String,
     prompt: String,
 }
 
+/// Format an optional metric for CSV, using "N/A" where no telemetry backend
+/// was available (e.g. no GPU, or an unsupported vendor).
+fn format_optional(value: Option<f64>) -> String {
+    value.map_or_else(|| "N/A".to_string(), |v| format!("{v:.2}"))
+}
+
+/// Format any `Display`-able optional metric for CSV, using "N/A" where no
+/// telemetry backend was available.
+fn format_optional_display(value: Option<impl std::fmt::Display>) -> String {
+    value.map_or_else(|| "N/A".to_string(), |v| v.to_string())
+}
+
 impl From<&BenchmarkMetrics> for CsvMetricRow {
     fn from(metric: &BenchmarkMetrics) -> Self {
         Self {
@@ -48,20 +90,44 @@ impl From<&BenchmarkMetrics> for CsvMetricRow {
             total_time_ms: format!("{:.2}", metric.total_response_time_ms),
             tokens_per_sec: format!("{:.2}", metric.tokens_per_second),
             avg_token_ms: format!("{:.2}", metric.avg_token_latency_ms),
+            final_token_ewma_tokens_per_sec: format_optional(metric.final_token_ewma_tokens_per_sec),
             timing_source: metric.timing_source.as_str().to_string(),
             memory_before_mb: format!("{:.2}", metric.memory_before_mb),
             memory_peak_mb: format!("{:.2}", metric.peak_memory_mb),
+            memory_virtual_mb: format!("{:.2}", metric.memory_breakdown.virtual_mb),
+            memory_private_mb: format_optional(metric.memory_breakdown.private_mb),
+            memory_shared_mb: format_optional(metric.memory_breakdown.shared_mb),
+            memory_file_backed_mb: format_optional(metric.memory_breakdown.file_backed_mb),
             // Comprehensive CPU metrics for Ollama vs llama.cpp comparison
             cpu_ollama_percent: format!("{:.2}", metric.cpu_ollama_percent),
             cpu_tauri_percent: format!("{:.2}", metric.cpu_tauri_percent),
             cpu_system_percent: format!("{:.2}", metric.cpu_system_percent),
             cpu_total_percent: format!("{:.2}", metric.cpu_total_percent),
+            cpu_tauri_measurement: metric.cpu_tauri_measurement.as_str().to_string(),
             response_tokens: metric.response_tokens,
             cpu_model: metric.cpu_model.clone(),
             gpu_name: metric.gpu_name.clone(),
             avx2_supported: metric.avx2_supported,
             npu_detected: metric.npu_detected,
             hardware_detection_failed: metric.hardware_detection_failed,
+            cpu_capability_score: format_optional(metric.cpu_capability_score),
+            memory_capability_score: format_optional(metric.memory_capability_score),
+            scaling_governor: format_optional_display(metric.scaling_governor.clone()),
+            boost_enabled: format_optional_display(metric.boost_enabled),
+            cpu_base_frequency_khz: format_optional_display(metric.cpu_base_frequency_khz),
+            cpu_freq_unsupported: metric.cpu_freq_unsupported,
+            gpu_utilization_percent: format_optional(metric.gpu_utilization_percent),
+            gpu_vram_used_mb: format_optional(metric.gpu_vram_used_mb),
+            gpu_power_watts: format_optional(metric.gpu_power_watts),
+            gpu_temperature_c: format_optional(metric.gpu_temperature_c),
+            ane_power_watts: format_optional(metric.ane_power_watts),
+            ane_residency_percent: format_optional(metric.ane_residency_percent),
+            system_memory_total_mb: format!("{:.2}", metric.system_memory_total_mb),
+            system_memory_avg_free_mb: format!("{:.2}", metric.system_memory_avg_free_mb),
+            system_memory_min_free_mb: format!("{:.2}", metric.system_memory_min_free_mb),
+            system_memory_avg_used_mb: format!("{:.2}", metric.system_memory_avg_used_mb),
+            system_swap_avg_used_mb: format!("{:.2}", metric.system_swap_avg_used_mb),
+            system_swap_peak_used_mb: format!("{:.2}", metric.system_swap_peak_used_mb),
             prompt: metric.prompt.clone(),
         }
     }
@@ -89,6 +155,39 @@ pub fn generate_filename(prefix: &str) -> String {
     format!("{prefix}-{timestamp}.csv")
 }
 
+/// Generate a JSON output filename with timestamp.
+pub fn generate_json_filename(prefix: &str) -> String {
+    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    format!("{prefix}-{timestamp}.json")
+}
+
+/// Which format(s) a caller wants from [`export_results`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Both,
+}
+
+/// Export `results` in the format(s) `format` selects, returning the path of
+/// every file written.
+pub fn export_results(
+    results: &BenchmarkResults,
+    prefix: &str,
+    format: ExportFormat,
+) -> Result<Vec<PathBuf>, String> {
+    let mut paths = Vec::new();
+
+    if matches!(format, ExportFormat::Csv | ExportFormat::Both) {
+        paths.push(export_to_csv(results, prefix)?);
+    }
+    if matches!(format, ExportFormat::Json | ExportFormat::Both) {
+        paths.push(export_to_json(results, prefix)?);
+    }
+
+    Ok(paths)
+}
+
 /// Export benchmark results to CSV using serde for automatic column management
 pub fn export_to_csv(results: &BenchmarkResults, prefix: &str) -> Result<PathBuf, String> {
     let benchmarks_dir = get_benchmarks_dir()?;
@@ -121,6 +220,176 @@ pub fn export_to_csv(results: &BenchmarkResults, prefix: &str) -> Result<PathBuf
     Ok(filepath)
 }
 
+/// Export benchmark results to JSON, preserving `results`'s native numeric
+/// types and full structure (metrics, summaries, `total_duration_seconds`,
+/// timestamp) instead of the flattened, 2-decimal-rounded string columns
+/// [`CsvMetricRow`] reports - easier to feed into `jq` or a plotting script
+/// than parsing CSV strings back into numbers.
+pub fn export_to_json(results: &BenchmarkResults, prefix: &str) -> Result<PathBuf, String> {
+    let benchmarks_dir = get_benchmarks_dir()?;
+    let filename = generate_json_filename(prefix);
+    let filepath = benchmarks_dir.join(&filename);
+
+    let json = serde_json::to_string_pretty(results)
+        .map_err(|e| format!("Failed to serialize benchmark results to JSON: {e}"))?;
+
+    fs::write(&filepath, json)
+        .map_err(|e| format!("Failed to write benchmark JSON file: {e}"))?;
+
+    Ok(filepath)
+}
+
+/// Primary timing metrics summarized into `summary.csv`, alongside the name
+/// each is reported under.
+const SUMMARY_METRICS: [(&str, fn(&BenchmarkMetrics) -> f64); 3] = [
+    ("first_token_ms", |m| m.first_token_latency_ms),
+    ("total_time_ms", |m| m.total_response_time_ms),
+    ("tokens_per_sec", |m| m.tokens_per_second),
+];
+
+/// One summarized `(category, model, metric)` row: a statistical rollup of
+/// every iteration sample in that group, rather than the raw per-iteration
+/// numbers `CsvMetricRow` reports.
+#[derive(Debug, Serialize)]
+struct SummaryRow {
+    category: String,
+    model: String,
+    metric: String,
+    sample_count: usize,
+    mean: String,
+    median: String,
+    std_dev: String,
+    ci_95_lower: String,
+    ci_95_upper: String,
+    mild_outliers: String,
+    severe_outliers: String,
+}
+
+impl SummaryRow {
+    fn new(category: &str, model: &str, metric: &str, stats: &MetricStats) -> Self {
+        let (ci_95_lower, ci_95_upper) = stats.ci_95.map_or(
+            ("N/A".to_string(), "N/A".to_string()),
+            |(lower, upper)| (format!("{lower:.2}"), format!("{upper:.2}")),
+        );
+
+        Self {
+            category: category.to_string(),
+            model: model.to_string(),
+            metric: metric.to_string(),
+            sample_count: stats.sample_count,
+            mean: format!("{:.2}", stats.mean),
+            median: format!("{:.2}", stats.median),
+            std_dev: format!("{:.2}", stats.std_dev),
+            ci_95_lower,
+            ci_95_upper,
+            mild_outliers: format_optional_display(stats.mild_outliers),
+            severe_outliers: format_optional_display(stats.severe_outliers),
+        }
+    }
+}
+
+/// Group `metrics` by `(category, model)` and statistically summarize each
+/// of [`SUMMARY_METRICS`] within every group via bootstrap confidence
+/// intervals and Tukey-fence outlier counts (see [`super::stats`]).
+///
+/// Grouping by `BTreeMap` (rather than a `HashMap`) gives `summary.csv` a
+/// deterministic row order across runs, which matters for diffing two
+/// exports against each other.
+fn stats(metrics: &[BenchmarkMetrics]) -> Vec<SummaryRow> {
+    let mut groups: BTreeMap<(String, String), Vec<&BenchmarkMetrics>> = BTreeMap::new();
+    for metric in metrics {
+        groups
+            .entry((metric.prompt_type.clone(), metric.model_name.clone()))
+            .or_default()
+            .push(metric);
+    }
+
+    let mut rows = Vec::with_capacity(groups.len() * SUMMARY_METRICS.len());
+    for ((category, model), group_metrics) in groups {
+        for (metric_name, extractor) in SUMMARY_METRICS {
+            let samples: Vec<f64> = group_metrics.iter().map(|m| extractor(m)).collect();
+            let metric_stats = stats::summarize(&samples);
+            rows.push(SummaryRow::new(&category, &model, metric_name, &metric_stats));
+        }
+    }
+
+    rows
+}
+
+/// Throughput and tail-latency metrics reported for a sustained-load run,
+/// alongside the name each is reported under.
+const SUSTAINED_LOAD_METRICS: [(&str, fn(&SustainedLoadResult) -> f64); 7] = [
+    ("sustained_achieved_ops_per_sec", |r| r.achieved_ops_per_sec),
+    ("sustained_p50_first_token_ms", |r| r.p50_first_token_ms),
+    ("sustained_p90_first_token_ms", |r| r.p90_first_token_ms),
+    ("sustained_p99_first_token_ms", |r| r.p99_first_token_ms),
+    ("sustained_p50_total_time_ms", |r| r.p50_total_time_ms),
+    ("sustained_p90_total_time_ms", |r| r.p90_total_time_ms),
+    ("sustained_p99_total_time_ms", |r| r.p99_total_time_ms),
+];
+
+/// Report a sustained-load run's throughput/percentiles as [`SummaryRow`]s so
+/// they flow through the same `summary.csv` a reader already knows how to
+/// read. Each is a single measured value rather than a statistical rollup
+/// across samples, so `mean`/`median` both report that value and `std_dev`/
+/// `ci_95_*`/outlier columns report "N/A".
+fn sustained_load_rows(result: &SustainedLoadResult) -> Vec<SummaryRow> {
+    SUSTAINED_LOAD_METRICS
+        .iter()
+        .map(|(metric_name, extractor)| {
+            let value = format!("{:.2}", extractor(result));
+            SummaryRow {
+                category: "sustained_load".to_string(),
+                model: result.model.clone(),
+                metric: metric_name.to_string(),
+                sample_count: result.requests_completed,
+                mean: value.clone(),
+                median: value,
+                std_dev: "N/A".to_string(),
+                ci_95_lower: "N/A".to_string(),
+                ci_95_upper: "N/A".to_string(),
+                mild_outliers: "N/A".to_string(),
+                severe_outliers: "N/A".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Export a `(category, model)` statistical summary of `results.metrics` to
+/// `summary.csv` - mean/median/std-dev always, plus a 95% bootstrap
+/// confidence interval and Tukey-fence outlier counts once a group has
+/// enough samples to support them (see [`super::stats`]). Comparing this
+/// file between a "baseline" and a "phase1" run answers "is this difference
+/// real?" without eyeballing noisy per-iteration numbers in the main CSV.
+///
+/// If `results.sustained_load` is present, its achieved throughput and
+/// p50/p90/p99 latency are appended as additional rows under the
+/// `sustained_load` category.
+pub fn export_summary_csv(results: &BenchmarkResults) -> Result<PathBuf, String> {
+    let benchmarks_dir = get_benchmarks_dir()?;
+    let filepath = benchmarks_dir.join("summary.csv");
+
+    let mut wtr = Writer::from_path(&filepath)
+        .map_err(|e| format!("Failed to create summary CSV file: {e}"))?;
+
+    for row in stats(&results.metrics) {
+        wtr.serialize(row)
+            .map_err(|e| format!("Failed to serialize summary row: {e}"))?;
+    }
+
+    if let Some(sustained_load) = &results.sustained_load {
+        for row in sustained_load_rows(sustained_load) {
+            wtr.serialize(row)
+                .map_err(|e| format!("Failed to serialize sustained-load summary row: {e}"))?;
+        }
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush summary CSV writer: {e}"))?;
+
+    Ok(filepath)
+}
+
 /// Create a README.md in the benchmarks directory explaining the CSV format
 pub fn create_readme() -> Result<(), String> {
     let benchmarks_dir = get_benchmarks_dir()?;
@@ -149,14 +418,39 @@ Files are named: `{prefix}-{timestamp}.csv`
 - **total_time_ms**: Total response generation time (ms)
 - **tokens_per_sec**: Real throughput based on streaming chunks (tokens/second)
 - **avg_token_ms**: Average time per token (ms)
+- **final_token_ewma_tokens_per_sec**: Final exponentially-weighted moving average of inter-token throughput (tokens/sec) - weights recent tokens more heavily than `tokens_per_sec`, so it reflects steady-state speed rather than being dragged down by a slow first token. `N/A` if the response had fewer than two streamed tokens.
 
 **Resource Metrics (Sampled every 100ms during inference)**
 - **memory_before_mb**: RAM before inference starts (MB)
 - **memory_during_mb**: Average RAM during inference (MB) - sampled periodically
 - **memory_peak_mb**: Peak RAM during inference (MB) - true peak from sampling
 - **memory_after_mb**: RAM after inference completes (MB)
+- **memory_virtual_mb**: Virtual memory size (MB), including unmapped/reserved address space
+- **memory_private_mb**: Pages private to this process, clean + dirty (MB) - roughly "this process's own heap and writable data". `N/A` off Linux
+- **memory_shared_mb**: Pages shared with another process, clean + dirty (MB). `N/A` off Linux
+- **memory_file_backed_mb**: Resident pages backed by a file rather than anonymous memory (MB) - for a GGUF model this is mostly mmapped weights. `N/A` off Linux
 - **cpu_percent**: Average CPU utilization during inference (%) - sampled periodically
 
+**GPU Metrics (Sampled alongside CPU/memory, NVIDIA/AMD/Apple Silicon only)**
+- **gpu_utilization_percent**: Average GPU utilization during inference (%) - `N/A` if no supported GPU/driver was detected
+- **gpu_vram_used_mb**: VRAM attributed to the Ollama process (MB) - `N/A` if unavailable (always `N/A` on Apple Silicon's unified memory)
+- **gpu_power_watts**: Average GPU power draw during inference (W) - `N/A` if unavailable
+- **gpu_temperature_c**: Average GPU temperature during inference (°C) - `N/A` if unavailable (always `N/A` on Apple Silicon)
+- **ane_power_watts**: Average Apple Neural Engine power draw during inference (W) - `N/A` off macOS or without `powermetrics` permissions
+- **ane_residency_percent**: Average Apple Neural Engine active residency during inference (%) - same `N/A` conditions as `ane_power_watts`
+
+**System Memory Metrics (Host-wide, sampled alongside CPU/memory)**
+- **system_memory_total_mb**: Total host RAM (MB)
+- **system_memory_avg_free_mb**: Average host-wide free/available RAM during inference (MB)
+- **system_memory_min_free_mb**: Minimum host-wide free/available RAM observed during inference (MB) - low values indicate memory pressure
+- **system_memory_avg_used_mb**: Average host-wide used RAM during inference (MB)
+- **system_swap_avg_used_mb**: Average swap used during inference (MB)
+- **system_swap_peak_used_mb**: Peak swap used during inference (MB) - non-zero values indicate the host was swapping
+
+**Hardware Capability Score (Reference-normalized, measured once per run)**
+- **cpu_capability_score**: Synthetic CPU micro-benchmark score, normalized against a fixed reference machine (~1.0 means "reference-class") - `N/A` if hardware detection failed
+- **memory_capability_score**: Synthetic memory-bandwidth micro-benchmark score, same normalization and `N/A` condition as `cpu_capability_score`
+
 **Test Metadata**
 - **timestamp**: ISO 8601 timestamp of test execution
 - **iteration**: Test iteration number (1-3 typically)
@@ -182,9 +476,53 @@ A background task samples CPU and memory every 100ms during inference to:
 ### Token Counting
 Tokens are counted from streaming chunks received from Ollama. This provides a more accurate count than character-based estimation, though it still approximates true tokenizer output.
 
+## summary.csv
+
+Alongside the timestamped per-run file, `summary.csv` is overwritten after
+every run with a statistical rollup grouped by `(category, model)`: for each
+of `first_token_ms`, `total_time_ms`, and `tokens_per_sec` it reports
+`sample_count`, `mean`, `median`, `std_dev`, a 95% bootstrap confidence
+interval (`ci_95_lower`/`ci_95_upper`), and Tukey-fence outlier counts
+(`mild_outliers`/`severe_outliers`). Groups with fewer than 4 samples report
+`N/A` for the CI and outlier columns - too few points to support a bootstrap
+distribution - and just the raw mean/median/std-dev. This is the file to
+diff when comparing two optimization phases, instead of eyeballing the
+per-iteration numbers above.
+
+## Sustained Load Mode
+
+Unlike the rest of the suite, which runs each prompt a small number of
+iterations sequentially, an optional sustained-load phase fires requests at a
+target operations-per-second for a fixed duration without waiting for prior
+requests to finish, the way windsock-style load tests do - queueing and
+backpressure under concurrency show up as rising tail latency instead of the
+benchmark self-throttling to match completion time. Opt in by passing a
+target rate and duration; when omitted, `results.sustained_load` is `None`
+and no extra rows are written.
+
+When present, its achieved throughput and p50/p90/p99 latency for first-token
+and total-response time are appended to `summary.csv` as
+`sustained_load`-category rows (`sustained_achieved_ops_per_sec`,
+`sustained_p50_first_token_ms`, `sustained_p90_first_token_ms`, etc.).
+Percentiles are computed via nearest-rank (sort, then index at
+`ceil(p/100 * n) - 1`, clamped), not the rounded `p/100 * (n-1)` index the
+coding-session latency percentiles use - nearest-rank is the conventional
+method for reporting load-test tail latency. The full result also round-trips through JSON export
+for free, since it's a field on `BenchmarkResults`.
+
+## JSON Export
+
+`export_to_json` writes the same run as `{prefix}-{timestamp}.json`: the full
+`BenchmarkResults` (metrics, summaries, `total_duration_seconds`, timestamp)
+with native numeric types, rather than the 2-decimal-rounded string columns
+the CSV uses. Prefer this over parsing the CSV back into numbers when feeding
+results into `jq` or a plotting script. `export_results` takes an
+`ExportFormat` (`Csv`, `Json`, or `Both`) so a caller can request either or
+both in one call.
+
 ## Usage
 
-Import CSV files into Excel, Google Sheets, or data analysis tools for visualization and comparison across optimization phases.
+Import CSV files into Excel, Google Sheets, or data analysis tools for visualization and comparison across optimization phases. Pipe JSON files into `jq` or a notebook for programmatic analysis.
 
 ## Limitations
 
@@ -203,7 +541,19 @@ Import CSV files into Excel, Google Sheets, or data analysis tools for visualiza
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::benchmark::metrics::{BenchmarkMetrics, TimingSource};
+    use crate::benchmark::metrics::{BenchmarkMetrics, CpuMeasurement, TimingSource};
+
+    fn zero_latency_percentiles() -> LatencyPercentiles {
+        LatencyPercentiles {
+            p50_first_token_ms: 0.0,
+            p95_first_token_ms: 0.0,
+            p99_first_token_ms: 0.0,
+            p50_total_response_ms: 0.0,
+            p95_total_response_ms: 0.0,
+            p99_total_response_ms: 0.0,
+            avg_tokens_per_second: 0.0,
+        }
+    }
 
     #[allow(deprecated)] // We need to set legacy cpu_usage_percent field
     fn create_test_metric() -> BenchmarkMetrics {
@@ -212,16 +562,25 @@ mod tests {
             total_response_time_ms: 1000.0,
             tokens_per_second: 10.0,
             avg_token_latency_ms: 100.0,
+            final_token_ewma_tokens_per_sec: Some(10.0),
             timing_source: TimingSource::Native,
             memory_before_mb: 1000.0,
             memory_during_mb: 1100.0,
             memory_after_mb: 1000.0,
             peak_memory_mb: 1200.0,
+            memory_breakdown: MemoryBreakdown {
+                resident_mb: 1200.0,
+                virtual_mb: 2400.0,
+                private_mb: Some(200.0),
+                shared_mb: Some(50.0),
+                file_backed_mb: Some(950.0),
+            },
             // New CPU metrics
             cpu_ollama_percent: 16.0,
             cpu_tauri_percent: 40.0,
             cpu_system_percent: 45.0,
             cpu_total_percent: 56.0,
+            cpu_tauri_measurement: CpuMeasurement::Sampled,
             cpu_usage_percent: 16.0, // Legacy field
             model_name: "test-model".to_string(),
             prompt_type: "short".to_string(),
@@ -234,6 +593,25 @@ mod tests {
             avx2_supported: true,
             npu_detected: false,
             hardware_detection_failed: false,
+            cpu_capability_score: Some(1.0),
+            memory_capability_score: Some(1.0),
+            scaling_governor: Some("performance".to_string()),
+            boost_enabled: Some(true),
+            cpu_base_frequency_khz: Some(3_000_000),
+            cpu_freq_unsupported: false,
+            gpu_utilization_percent: Some(30.0),
+            gpu_vram_used_mb: Some(4096.0),
+            gpu_power_watts: Some(120.0),
+            gpu_temperature_c: Some(65.0),
+            ane_power_watts: None,
+            ane_residency_percent: None,
+            system_memory_total_mb: 8192.0,
+            system_memory_avg_free_mb: 4096.0,
+            system_memory_min_free_mb: 3000.0,
+            system_memory_avg_used_mb: 4096.0,
+            system_swap_avg_used_mb: 0.0,
+            system_swap_peak_used_mb: 0.0,
+            resource_timeseries: Vec::new(),
         }
     }
 
@@ -261,14 +639,23 @@ mod tests {
             total_response_time_ms: 1234.567,
             tokens_per_second: 12.345,
             avg_token_latency_ms: 98.765,
+            final_token_ewma_tokens_per_sec: Some(11.111),
             memory_before_mb: 1000.123,
             memory_during_mb: 1100.456,
             memory_after_mb: 1000.789,
             peak_memory_mb: 1200.987,
+            memory_breakdown: MemoryBreakdown {
+                resident_mb: 1200.987,
+                virtual_mb: 2400.5,
+                private_mb: Some(200.222),
+                shared_mb: Some(50.111),
+                file_backed_mb: Some(950.654),
+            },
             cpu_ollama_percent: 16.123,
             cpu_tauri_percent: 40.456,
             cpu_system_percent: 45.789,
             cpu_total_percent: 56.579,
+            cpu_tauri_measurement: CpuMeasurement::Sampled,
             cpu_usage_percent: 16.123, // Legacy field
             model_name: "test".to_string(),
             prompt_type: "medium".to_string(),
@@ -281,6 +668,25 @@ mod tests {
             avx2_supported: true,
             npu_detected: false,
             hardware_detection_failed: false,
+            cpu_capability_score: Some(1.0),
+            memory_capability_score: Some(1.0),
+            scaling_governor: None,
+            boost_enabled: None,
+            cpu_base_frequency_khz: None,
+            cpu_freq_unsupported: true,
+            gpu_utilization_percent: Some(30.456),
+            gpu_vram_used_mb: None,
+            gpu_power_watts: Some(120.654),
+            gpu_temperature_c: None,
+            ane_power_watts: Some(1.234),
+            ane_residency_percent: None,
+            system_memory_total_mb: 8192.123,
+            system_memory_avg_free_mb: 4096.456,
+            system_memory_min_free_mb: 3000.789,
+            system_memory_avg_used_mb: 4096.321,
+            system_swap_avg_used_mb: 12.345,
+            system_swap_peak_used_mb: 56.789,
+            resource_timeseries: Vec::new(),
             timing_source: TimingSource::Native,
         };
 
@@ -291,11 +697,31 @@ mod tests {
         assert_eq!(csv_row.total_time_ms, "1234.57");
         assert_eq!(csv_row.tokens_per_sec, "12.35");
         assert_eq!(csv_row.memory_peak_mb, "1200.99");
+        assert_eq!(csv_row.memory_virtual_mb, "2400.50");
+        assert_eq!(csv_row.memory_private_mb, "200.22");
+        assert_eq!(csv_row.memory_shared_mb, "50.11");
+        assert_eq!(csv_row.memory_file_backed_mb, "950.65");
         // Verify CPU metrics formatting
         assert_eq!(csv_row.cpu_ollama_percent, "16.12");
         assert_eq!(csv_row.cpu_tauri_percent, "40.46");
         assert_eq!(csv_row.cpu_system_percent, "45.79");
         assert_eq!(csv_row.cpu_total_percent, "56.58");
+        // Verify GPU metrics: formatted when present, "N/A" when telemetry was unavailable
+        assert_eq!(csv_row.gpu_utilization_percent, "30.46");
+        assert_eq!(csv_row.gpu_vram_used_mb, "N/A");
+        assert_eq!(csv_row.gpu_power_watts, "120.65");
+        assert_eq!(csv_row.gpu_temperature_c, "N/A");
+        // Verify ANE metrics: formatted when present, "N/A" when telemetry was unavailable
+        assert_eq!(csv_row.ane_power_watts, "1.23");
+        assert_eq!(csv_row.ane_residency_percent, "N/A");
+        // Verify CPU frequency-scaling metadata: "N/A" when unsupported on this host
+        assert_eq!(csv_row.scaling_governor, "N/A");
+        assert_eq!(csv_row.boost_enabled, "N/A");
+        assert_eq!(csv_row.cpu_base_frequency_khz, "N/A");
+        assert!(csv_row.cpu_freq_unsupported);
+        // Verify system memory metrics formatting
+        assert_eq!(csv_row.system_memory_min_free_mb, "3000.79");
+        assert_eq!(csv_row.system_swap_peak_used_mb, "56.79");
     }
 
     #[test]
@@ -317,8 +743,16 @@ mod tests {
         let results = BenchmarkResults {
             metrics: vec![metric],
             summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
             total_duration_seconds: 10.0,
             timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
         };
 
         let result = export_to_csv(&results, "test");
@@ -339,8 +773,16 @@ mod tests {
         let results = BenchmarkResults {
             metrics: vec![metric],
             summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
             total_duration_seconds: 10.0,
             timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
         };
 
         // Use unique prefix to avoid test interference
@@ -376,8 +818,16 @@ mod tests {
                 },
             ],
             summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
             total_duration_seconds: 20.0,
             timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
         };
 
         // Use unique prefix to avoid test interference
@@ -407,4 +857,142 @@ mod tests {
         assert!(path.is_dir(), "Path should be a directory");
         assert_eq!(path.file_name().unwrap(), "benchmarks");
     }
+
+    #[test]
+    fn test_stats_groups_by_category_and_model() {
+        let metrics = vec![
+            BenchmarkMetrics {
+                prompt_type: "short".to_string(),
+                model_name: "model-a".to_string(),
+                ..create_test_metric()
+            },
+            BenchmarkMetrics {
+                prompt_type: "short".to_string(),
+                model_name: "model-b".to_string(),
+                ..create_test_metric()
+            },
+            BenchmarkMetrics {
+                prompt_type: "medium".to_string(),
+                model_name: "model-a".to_string(),
+                ..create_test_metric()
+            },
+        ];
+
+        let rows = stats(&metrics);
+
+        // 3 groups * 3 summarized metrics each
+        assert_eq!(rows.len(), 9);
+        assert!(rows.iter().any(|r| r.category == "short" && r.model == "model-a"));
+        assert!(rows.iter().any(|r| r.category == "short" && r.model == "model-b"));
+        assert!(rows.iter().any(|r| r.category == "medium" && r.model == "model-a"));
+    }
+
+    #[test]
+    fn test_stats_below_min_samples_reports_na_ci() {
+        // Only 2 samples in the group - below the bootstrap CI threshold.
+        let metrics = vec![create_test_metric(), create_test_metric()];
+
+        let rows = stats(&metrics);
+        let first_token_row = rows
+            .iter()
+            .find(|r| r.metric == "first_token_ms")
+            .expect("should have a first_token_ms row");
+
+        assert_eq!(first_token_row.sample_count, 2);
+        assert_eq!(first_token_row.ci_95_lower, "N/A");
+        assert_eq!(first_token_row.ci_95_upper, "N/A");
+        assert_eq!(first_token_row.mild_outliers, "N/A");
+        assert_eq!(first_token_row.severe_outliers, "N/A");
+    }
+
+    #[test]
+    fn test_export_summary_csv_creates_file_with_header() {
+        let results = BenchmarkResults {
+            metrics: vec![create_test_metric()],
+            summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
+            total_duration_seconds: 10.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
+        };
+
+        let filepath = export_summary_csv(&results).expect("summary export should succeed");
+        assert!(filepath.exists());
+        assert_eq!(filepath.file_name().unwrap(), "summary.csv");
+
+        let content = std::fs::read_to_string(&filepath).unwrap();
+        assert!(content.contains("category,model,metric,sample_count,mean,median,std_dev"));
+        assert!(content.contains("short"));
+        assert!(content.contains("test-model"));
+
+        let _ = std::fs::remove_file(&filepath);
+    }
+
+    #[test]
+    fn test_export_to_json_preserves_numeric_types() {
+        let results = BenchmarkResults {
+            metrics: vec![create_test_metric()],
+            summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
+            total_duration_seconds: 10.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
+        };
+
+        let prefix = format!("test-json-{}", std::process::id());
+        let filepath = export_to_json(&results, &prefix).expect("JSON export should succeed");
+
+        assert!(filepath.exists());
+        assert_eq!(filepath.extension().unwrap(), "json");
+
+        let content = std::fs::read_to_string(&filepath).unwrap();
+        let parsed: BenchmarkResults =
+            serde_json::from_str(&content).expect("exported JSON should round-trip");
+        assert_eq!(parsed.metrics.len(), 1);
+        assert_eq!(parsed.total_duration_seconds, 10.0);
+
+        let _ = std::fs::remove_file(&filepath);
+    }
+
+    #[test]
+    fn test_export_results_both_writes_csv_and_json() {
+        let results = BenchmarkResults {
+            metrics: vec![create_test_metric()],
+            summary: vec![],
+            steady_state_summary: vec![],
+            warmup_iterations: 0,
+            total_duration_seconds: 10.0,
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            cgroup_accounting: false,
+            peak_memory_bytes: None,
+            cpu_time_ms: None,
+            thermal_throttling_suspected: false,
+            coding_session_latency_percentiles: zero_latency_percentiles(),
+            sustained_load: None,
+        };
+
+        let prefix = format!("test-both-{}", std::process::id());
+        let paths = export_results(&results, &prefix, ExportFormat::Both)
+            .expect("combined export should succeed");
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().any(|p| p.extension().unwrap() == "csv"));
+        assert!(paths.iter().any(|p| p.extension().unwrap() == "json"));
+
+        for path in &paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
 }
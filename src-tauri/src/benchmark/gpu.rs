@@ -0,0 +1,315 @@
+//! GPU telemetry sampling for benchmark runs.
+//!
+//! Polls utilization, VRAM, power, and temperature alongside the CPU/memory
+//! sampler in `sampling.rs`. Dispatches on the vendor reported by
+//! `hardware::detector::convert_gpu_info` (NVML for NVIDIA, ROCm SMI for AMD,
+//! `powermetrics` for Apple Silicon); other vendors degrade to `None` fields,
+//! mirroring the `hardware_detection_failed` convention used elsewhere in the
+//! benchmark subsystem.
+//!
+//! `pub(crate)` rather than private so `hardware::monitor`'s live telemetry
+//! loop can reuse the same vendor-dispatched samplers instead of
+//! reimplementing NVML/rocm-smi/powermetrics polling a second time.
+
+use crate::hardware::types::GpuVendor;
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+const MILLIWATTS_PER_WATT: f64 = 1000.0;
+
+/// One GPU telemetry sample. Fields are `None` when the backend couldn't
+/// report that particular metric (e.g. VRAM not attributable to Ollama's PID).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GpuSample {
+    pub utilization_percent: Option<f64>,
+    pub vram_used_mb: Option<f64>,
+    pub power_watts: Option<f64>,
+    pub temperature_c: Option<f64>,
+    /// Apple Neural Engine power draw (W), from the `powermetrics` ANE energy
+    /// counter. `None` off macOS, or when the process lacks the permissions
+    /// `powermetrics` requires.
+    pub ane_power_watts: Option<f64>,
+    /// Apple Neural Engine active residency (%), same source and caveats as
+    /// `ane_power_watts`.
+    pub ane_residency_percent: Option<f64>,
+}
+
+/// Polls GPU telemetry for whichever backend matches the detected vendor.
+pub enum GpuSampler {
+    Nvidia(NvidiaGpuSampler),
+    Amd(AmdGpuSampler),
+    #[cfg(target_os = "macos")]
+    Apple(AppleGpuSampler),
+    /// No backend for this vendor, or the driver/library wasn't available.
+    Unsupported,
+}
+
+impl GpuSampler {
+    /// Build a sampler for the given vendor. Falls back to `Unsupported` (rather
+    /// than failing the benchmark) when initialization fails, so CPU-only and
+    /// unsupported-vendor machines still produce a full set of results.
+    pub fn new(vendor: &GpuVendor) -> Self {
+        match vendor {
+            GpuVendor::Nvidia => match NvidiaGpuSampler::new() {
+                Ok(sampler) => GpuSampler::Nvidia(sampler),
+                Err(e) => {
+                    log::warn!("NVML initialization failed, GPU telemetry disabled: {e}");
+                    GpuSampler::Unsupported
+                }
+            },
+            GpuVendor::Amd => match AmdGpuSampler::new() {
+                Ok(sampler) => GpuSampler::Amd(sampler),
+                Err(e) => {
+                    log::warn!("rocm-smi unavailable, GPU telemetry disabled: {e}");
+                    GpuSampler::Unsupported
+                }
+            },
+            GpuVendor::Apple => Self::new_apple(),
+            GpuVendor::Intel | GpuVendor::Qualcomm | GpuVendor::Unknown => GpuSampler::Unsupported,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn new_apple() -> Self {
+        match AppleGpuSampler::new() {
+            Ok(sampler) => GpuSampler::Apple(sampler),
+            Err(e) => {
+                log::warn!("powermetrics unavailable, GPU/ANE telemetry disabled: {e}");
+                GpuSampler::Unsupported
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn new_apple() -> Self {
+        GpuSampler::Unsupported
+    }
+
+    /// Sample current GPU telemetry, attributing VRAM to `ollama_pid` where possible.
+    pub fn sample(&self, ollama_pid: sysinfo::Pid) -> GpuSample {
+        match self {
+            GpuSampler::Nvidia(sampler) => sampler.sample(ollama_pid),
+            GpuSampler::Amd(sampler) => sampler.sample(),
+            #[cfg(target_os = "macos")]
+            GpuSampler::Apple(sampler) => sampler.sample(),
+            GpuSampler::Unsupported => GpuSample::default(),
+        }
+    }
+}
+
+/// NVML-backed sampler for NVIDIA GPUs.
+pub struct NvidiaGpuSampler {
+    nvml: nvml_wrapper::Nvml,
+}
+
+impl NvidiaGpuSampler {
+    fn new() -> Result<Self, String> {
+        let nvml = nvml_wrapper::Nvml::init().map_err(|e| e.to_string())?;
+        Ok(Self { nvml })
+    }
+
+    /// Sample device 0. Multi-GPU attribution isn't attempted since Ollama
+    /// doesn't expose which device it placed the active model on.
+    fn sample(&self, ollama_pid: sysinfo::Pid) -> GpuSample {
+        let device = match self.nvml.device_by_index(0) {
+            Ok(device) => device,
+            Err(e) => {
+                log::warn!("Failed to access NVIDIA GPU 0: {e}");
+                return GpuSample::default();
+            }
+        };
+
+        let utilization_percent = device.utilization_rates().ok().map(|u| f64::from(u.gpu));
+        let power_watts = device.power_usage().ok().map(|mw| f64::from(mw) / MILLIWATTS_PER_WATT);
+        let temperature_c = device
+            .temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
+            .ok()
+            .map(f64::from);
+
+        // Attribute VRAM to the Ollama process specifically, since other
+        // processes (compositor, other loaded models) can share the device.
+        let vram_used_mb = device
+            .running_compute_processes()
+            .ok()
+            .and_then(|procs| procs.into_iter().find(|p| p.pid == ollama_pid.as_u32()))
+            .and_then(|p| match p.used_gpu_memory {
+                nvml_wrapper::enums::device::UsedGpuMemory::Used(bytes) => {
+                    Some(bytes as f64 / BYTES_PER_MB)
+                }
+                nvml_wrapper::enums::device::UsedGpuMemory::Unavailable => None,
+            });
+
+        GpuSample {
+            utilization_percent,
+            vram_used_mb,
+            power_watts,
+            temperature_c,
+            ane_power_watts: None,
+            ane_residency_percent: None,
+        }
+    }
+}
+
+/// `rocm-smi`-backed sampler for AMD GPUs. Shells out rather than linking
+/// against the ROCm SMI library directly, since that library isn't available
+/// as a published Rust binding.
+pub struct AmdGpuSampler;
+
+impl AmdGpuSampler {
+    fn new() -> Result<Self, String> {
+        // Probe availability up front so callers fall back to `Unsupported`
+        // once, instead of re-discovering a missing binary on every sample.
+        std::process::Command::new("rocm-smi")
+            .arg("--showuse")
+            .output()
+            .map_err(|e| format!("rocm-smi not found on PATH: {e}"))?;
+        Ok(Self)
+    }
+
+    fn sample(&self) -> GpuSample {
+        let output = match std::process::Command::new("rocm-smi")
+            .args([
+                "--showuse",
+                "--showmeminfo",
+                "vram",
+                "--showpower",
+                "--showtemp",
+                "--json",
+            ])
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                log::warn!("rocm-smi invocation failed: {e}");
+                return GpuSample::default();
+            }
+        };
+
+        parse_rocm_smi_json(&output.stdout).unwrap_or_else(|| {
+            log::warn!("Failed to parse rocm-smi JSON output");
+            GpuSample::default()
+        })
+    }
+}
+
+/// Parse `rocm-smi --json` output for the first card's telemetry.
+///
+/// rocm-smi reports numeric fields as strings (e.g. `"45.0"`, `"62"`), so each
+/// field is parsed individually rather than relying on serde's numeric typing.
+fn parse_rocm_smi_json(bytes: &[u8]) -> Option<GpuSample> {
+    let value: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+    let card = value.as_object()?.values().next()?;
+
+    let parse_field = |key: &str| -> Option<f64> {
+        card.get(key)?.as_str()?.trim().parse::<f64>().ok()
+    };
+
+    Some(GpuSample {
+        utilization_percent: parse_field("GPU use (%)"),
+        vram_used_mb: parse_field("VRAM Total Used Memory (B)").map(|bytes| bytes / BYTES_PER_MB),
+        power_watts: parse_field("Average Graphics Package Power (W)"),
+        temperature_c: parse_field("Temperature (Sensor edge) (C)"),
+        ane_power_watts: None,
+        ane_residency_percent: None,
+    })
+}
+
+/// `powermetrics`-backed sampler for Apple Silicon's integrated GPU and Neural
+/// Engine. Shells out rather than linking IOReport directly, since IOReport is
+/// a private framework with no published Rust binding (same tradeoff as
+/// [`AmdGpuSampler`] shelling out to `rocm-smi`).
+///
+/// `powermetrics` requires root or the `com.apple.private.iokit.powermetrics.samplers`
+/// entitlement; on an unprivileged Tauri process every field degrades to `None`
+/// rather than failing the benchmark.
+#[cfg(target_os = "macos")]
+pub struct AppleGpuSampler;
+
+#[cfg(target_os = "macos")]
+impl AppleGpuSampler {
+    /// Single-sample interval for `powermetrics -i`, matching the 50ms
+    /// `SAMPLING_INTERVAL` in `sampling.rs` as closely as `powermetrics` allows.
+    const SAMPLE_INTERVAL_MS: u32 = 50;
+
+    fn new() -> Result<Self, String> {
+        // Probe availability/permissions up front so callers fall back to
+        // `Unsupported` once, instead of re-discovering missing entitlements
+        // on every sample.
+        let output = std::process::Command::new("powermetrics")
+            .args(["--samplers", "gpu_power,ane_power", "-i", "1", "-n", "1"])
+            .output()
+            .map_err(|e| format!("powermetrics not found on PATH: {e}"))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "powermetrics exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(Self)
+    }
+
+    fn sample(&self) -> GpuSample {
+        let output = match std::process::Command::new("powermetrics")
+            .args([
+                "--samplers",
+                "gpu_power,ane_power",
+                "-i",
+                &Self::SAMPLE_INTERVAL_MS.to_string(),
+                "-n",
+                "1",
+            ])
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                log::warn!("powermetrics invocation failed: {e}");
+                return GpuSample::default();
+            }
+        };
+
+        if !output.status.success() {
+            log::warn!(
+                "powermetrics exited with {} (likely lacks permissions): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return GpuSample::default();
+        }
+
+        parse_powermetrics_text(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Parse `powermetrics --samplers gpu_power,ane_power` default text output.
+///
+/// The text sampler (not `-f plist`) prints labeled lines like
+/// `GPU HW active residency:  12.34%` and `ANE Power: 123 mW`; each field is
+/// pulled independently so a missing line (e.g. no ANE on this chip) just
+/// leaves that field `None` rather than failing the whole sample.
+#[cfg(target_os = "macos")]
+fn parse_powermetrics_text(text: &str) -> GpuSample {
+    let field_after = |label: &str| -> Option<f64> {
+        text.lines()
+            .find(|line| line.contains(label))
+            .and_then(|line| line.split(':').nth(1))
+            .and_then(|rest| {
+                rest.trim()
+                    .trim_end_matches('%')
+                    .trim_end_matches("mW")
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+            })
+    };
+
+    GpuSample {
+        utilization_percent: field_after("GPU HW active residency"),
+        vram_used_mb: None, // unified memory: no Ollama-attributable VRAM figure
+        power_watts: field_after("GPU Power").map(|mw| mw / MILLIWATTS_PER_WATT),
+        temperature_c: None, // not exposed by the gpu_power/ane_power samplers
+        ane_power_watts: field_after("ANE Power").map(|mw| mw / MILLIWATTS_PER_WATT),
+        ane_residency_percent: field_after("ANE HW active residency"),
+    }
+}
@@ -0,0 +1,150 @@
+//! HDR histogram-backed latency distributions.
+//!
+//! Arithmetic means (`BenchmarkSummary::avg_first_token_ms` etc.) hide tail
+//! behavior that matters for interactive code assistance - a model that's
+//! fast on average but spikes to 5s on 1-in-20 requests feels broken even
+//! though its mean looks fine. `hdrhistogram::Histogram` tracks the full
+//! distribution at fixed relative precision so p50/p95/p99/max can be read
+//! back cheaply, and the raw histogram can be serialized alongside the run
+//! so it can be re-aggregated later (e.g. merged across runs) without
+//! re-benchmarking.
+
+use hdrhistogram::serialization::{Deserializer, Serializer, V2Serializer};
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Deserializer as SerdeDeserializer, Serialize, Serializer as SerdeSerializer};
+
+/// Smallest value (integer microseconds) the histogram can distinguish.
+const HISTOGRAM_LOWEST_DISCERNIBLE_VALUE: u64 = 1;
+/// Largest value (integer microseconds) the histogram can record - one hour,
+/// far beyond any realistic single request's latency.
+const HISTOGRAM_HIGHEST_TRACKABLE_VALUE: u64 = 3_600_000_000;
+/// Number of significant decimal digits of precision to preserve.
+const HISTOGRAM_SIGNIFICANT_DIGITS: u8 = 3;
+
+/// Scale a millisecond latency to the integer microseconds the histogram
+/// records, preserving sub-millisecond precision that an integer-ms
+/// histogram would round away.
+fn ms_to_histogram_units(ms: f64) -> u64 {
+    (ms * 1000.0).round().max(0.0) as u64
+}
+
+/// A fresh histogram sized for recording request latencies in milliseconds.
+pub fn new_latency_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(
+        HISTOGRAM_LOWEST_DISCERNIBLE_VALUE,
+        HISTOGRAM_HIGHEST_TRACKABLE_VALUE,
+        HISTOGRAM_SIGNIFICANT_DIGITS,
+    )
+    .expect("fixed histogram bounds/precision should always be valid")
+}
+
+/// Record a millisecond latency sample. Values outside the histogram's
+/// trackable range are silently dropped rather than failing the whole
+/// summary - an out-of-range sample here is a sign of a bug elsewhere, not
+/// something a benchmark run should crash over.
+pub fn record_ms(histogram: &mut Histogram<u64>, ms: f64) {
+    let _ = histogram.record(ms_to_histogram_units(ms));
+}
+
+/// p50/p95/p99/max of a latency distribution, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LatencyDistribution {
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Read [`LatencyDistribution`] off a histogram built by [`record_ms`].
+pub fn distribution_from_histogram(histogram: &Histogram<u64>) -> LatencyDistribution {
+    LatencyDistribution {
+        p50_ms: histogram.value_at_percentile(50.0) as f64 / 1000.0,
+        p95_ms: histogram.value_at_percentile(95.0) as f64 / 1000.0,
+        p99_ms: histogram.value_at_percentile(99.0) as f64 / 1000.0,
+        max_ms: histogram.max() as f64 / 1000.0,
+    }
+}
+
+/// Wraps `hdrhistogram::Histogram<u64>` so the raw distribution - not just
+/// the percentiles read off it - can be stored in `BenchmarkResults` and
+/// re-aggregated later (e.g. merged across runs) without re-running the
+/// benchmark. `Histogram` itself doesn't implement `serde::Serialize`, so
+/// this (de)serializes via the crate's own compressed V2 wire format.
+#[derive(Debug, Clone)]
+pub struct SerializableHistogram(pub Histogram<u64>);
+
+impl Serialize for SerializableHistogram {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: SerdeSerializer,
+    {
+        let mut buf = Vec::new();
+        V2Serializer::new()
+            .serialize(&self.0, &mut buf)
+            .map_err(|e| serde::ser::Error::custom(format!("failed to serialize histogram: {e}")))?;
+        serializer.serialize_bytes(&buf)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerializableHistogram {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: SerdeDeserializer<'de>,
+    {
+        let buf = Vec::<u8>::deserialize(deserializer)?;
+        let histogram = Deserializer::new()
+            .deserialize(&mut &buf[..])
+            .map_err(|e| serde::de::Error::custom(format!("failed to deserialize histogram: {e}")))?;
+        Ok(SerializableHistogram(histogram))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribution_from_histogram_percentiles() {
+        let mut histogram = new_latency_histogram();
+        for ms in 1..=100 {
+            record_ms(&mut histogram, ms as f64);
+        }
+
+        let distribution = distribution_from_histogram(&histogram);
+
+        assert!((distribution.p50_ms - 50.0).abs() < 1.0);
+        assert!((distribution.p95_ms - 95.0).abs() < 1.0);
+        assert!((distribution.p99_ms - 99.0).abs() < 1.0);
+        // max() reports the highest value equivalent to 100ms at 3 significant digits.
+        assert_eq!(distribution.max_ms, 100.031);
+    }
+
+    #[test]
+    fn test_serializable_histogram_round_trips() {
+        let mut histogram = new_latency_histogram();
+        record_ms(&mut histogram, 123.456);
+        record_ms(&mut histogram, 789.012);
+
+        let wrapped = SerializableHistogram(histogram);
+        let json = serde_json::to_string(&wrapped).expect("should serialize");
+        let restored: SerializableHistogram =
+            serde_json::from_str(&json).expect("should deserialize");
+
+        let original_dist = distribution_from_histogram(&wrapped.0);
+        let restored_dist = distribution_from_histogram(&restored.0);
+        assert_eq!(original_dist, restored_dist);
+    }
+
+    #[test]
+    fn test_record_ms_ignores_out_of_range_values() {
+        let mut histogram = new_latency_histogram();
+        // Negative-after-rounding and absurdly large values shouldn't panic.
+        record_ms(&mut histogram, -5.0);
+        record_ms(&mut histogram, f64::MAX);
+        record_ms(&mut histogram, 10.0);
+
+        let distribution = distribution_from_histogram(&histogram);
+        // Highest value equivalent to 10ms at 3 significant digits.
+        assert_eq!(distribution.max_ms, 10.007);
+    }
+}
@@ -0,0 +1,99 @@
+//! Structured per-process memory breakdown, beyond a single RSS figure.
+//!
+//! A single RSS figure can't tell you *why* one model's memory cost differs
+//! from another's - heap allocations vs mmapped model weights vs pages
+//! shared with another process all show up as the same number. On Linux,
+//! `/proc/<pid>/smaps_rollup` exposes the private/shared and anonymous/
+//! file-backed split the kernel already tracks per-mapping, aggregated
+//! across the whole process; elsewhere (or if that file isn't readable)
+//! this falls back to `sysinfo`'s resident/virtual figures only, same as
+//! `cgroup.rs` falls back to `sysinfo`-sampled CPU/memory off Linux.
+
+use sysinfo::System;
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// A structured memory breakdown for one process at a point in time.
+///
+/// `private_mb`/`shared_mb`/`file_backed_mb` are `None` where the host
+/// doesn't expose `smaps_rollup` (non-Linux, or unreadable for this PID) -
+/// `resident_mb`/`virtual_mb` are always available from `sysinfo`.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct MemoryBreakdown {
+    /// Resident set size (MB) - same figure `memory_*_mb` already reports.
+    pub resident_mb: f64,
+    /// Virtual memory size (MB), including unmapped/reserved address space.
+    pub virtual_mb: f64,
+    /// Private (not shared with another process) pages, clean + dirty (MB).
+    /// Roughly "this process's own heap and writable data".
+    pub private_mb: Option<f64>,
+    /// Pages shared with another process, clean + dirty (MB). A second
+    /// Ollama request handler or a shared library mapping would count here.
+    pub shared_mb: Option<f64>,
+    /// Resident pages backed by a file rather than anonymous memory (MB) -
+    /// for a GGUF model this is mostly the mmapped weights, which is why it
+    /// can dominate RSS without costing "real" heap memory the same way an
+    /// in-process llama.cpp backend's allocations would.
+    pub file_backed_mb: Option<f64>,
+}
+
+impl MemoryBreakdown {
+    /// Capture a breakdown for `pid` from an already-refreshed `sys`.
+    /// Returns `None` if `pid` isn't found in `sys`.
+    pub fn capture(pid: sysinfo::Pid, sys: &System) -> Option<Self> {
+        let process = sys.process(pid)?;
+        let resident_mb = (process.memory() as f64) / BYTES_PER_MB;
+        let virtual_mb = (process.virtual_memory() as f64) / BYTES_PER_MB;
+
+        let rollup = read_smaps_rollup(pid);
+
+        Some(Self {
+            resident_mb,
+            virtual_mb,
+            private_mb: rollup.map(|r| r.private_mb),
+            shared_mb: rollup.map(|r| r.shared_mb),
+            file_backed_mb: rollup.map(|r| r.file_backed_mb),
+        })
+    }
+}
+
+struct SmapsRollup {
+    private_mb: f64,
+    shared_mb: f64,
+    file_backed_mb: f64,
+}
+
+/// Parse `/proc/<pid>/smaps_rollup`'s private/shared/anonymous totals.
+/// `None` on cgroup-unreadable PIDs, permission-denied, or kernels too old
+/// to expose the rollup file (pre-4.14).
+#[cfg(target_os = "linux")]
+fn read_smaps_rollup(pid: sysinfo::Pid) -> Option<SmapsRollup> {
+    let text = std::fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?;
+
+    let field_kb = |name: &str| -> Option<f64> {
+        text.lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|rest| rest.trim().strip_suffix(" kB"))
+            .and_then(|kb| kb.trim().parse::<f64>().ok())
+    };
+
+    let rss_kb = field_kb("Rss:")?;
+    let anonymous_kb = field_kb("Anonymous:")?;
+    let shared_clean_kb = field_kb("Shared_Clean:")?;
+    let shared_dirty_kb = field_kb("Shared_Dirty:")?;
+    let private_clean_kb = field_kb("Private_Clean:")?;
+    let private_dirty_kb = field_kb("Private_Dirty:")?;
+
+    Some(SmapsRollup {
+        private_mb: (private_clean_kb + private_dirty_kb) / 1024.0,
+        shared_mb: (shared_clean_kb + shared_dirty_kb) / 1024.0,
+        // Resident pages backed by a file are whatever isn't anonymous -
+        // smaps_rollup has no direct "file-backed" total of its own.
+        file_backed_mb: (rss_kb - anonymous_kb).max(0.0) / 1024.0,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_smaps_rollup(_pid: sysinfo::Pid) -> Option<SmapsRollup> {
+    None
+}
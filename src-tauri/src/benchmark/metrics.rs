@@ -1,5 +1,14 @@
+use super::histogram::{distribution_from_histogram, new_latency_histogram, record_ms, LatencyDistribution, SerializableHistogram};
+use super::memory_breakdown::MemoryBreakdown;
+use super::sampling::{calculate_average, calculate_percentile, ResourceSamplePoint};
+use super::stats::autocorrelation_corrected_ci;
+use super::sustained_load::SustainedLoadResult;
 use serde::{Deserialize, Serialize};
 
+/// Confidence level used for `BenchmarkSummary`'s autocorrelation-corrected
+/// CIs on the primary timing averages.
+const SUMMARY_CI_CONFIDENCE: f64 = 0.95;
+
 /// Source of timing data for benchmark metrics
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TimingSource {
@@ -18,6 +27,28 @@ impl TimingSource {
     }
 }
 
+/// Source of a process's CPU-usage measurement.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CpuMeasurement {
+    /// Periodic %-sampling (see `sampling.rs`) - can miss a CPU burst that
+    /// happens entirely between two samples.
+    Sampled,
+    /// Exact average utilization from the POSIX process CPU clock
+    /// (`cpu_time::ProcessTime`): consumed CPU-seconds over wall-clock
+    /// seconds for the whole test, with no sampling gap. Only available for
+    /// processes this crate controls directly (see `cpu_clock.rs`).
+    ProcessClock,
+}
+
+impl CpuMeasurement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CpuMeasurement::Sampled => "sampled",
+            CpuMeasurement::ProcessClock => "process_clock",
+        }
+    }
+}
+
 /// Performance metrics for a single benchmark test
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkMetrics {
@@ -34,6 +65,14 @@ pub struct BenchmarkMetrics {
     /// Average time per token (ms)
     pub avg_token_latency_ms: f64,
 
+    /// Final exponentially-weighted moving average of inter-token latency,
+    /// converted to tokens/sec (see `token_ewma.rs`). Unlike
+    /// `avg_token_latency_ms` (a flat average over the whole response), this
+    /// weights recent tokens more heavily, so it reflects steady-state
+    /// throughput rather than being dragged down by a slow first token.
+    /// `None` if the response had fewer than two streamed tokens.
+    pub final_token_ewma_tokens_per_sec: Option<f64>,
+
     /// Source of timing data ("native" = Ollama's metrics, "client" = client-side fallback)
     pub timing_source: TimingSource,
 
@@ -50,6 +89,11 @@ pub struct BenchmarkMetrics {
     /// Peak RAM usage during test (MB)
     pub peak_memory_mb: f64,
 
+    /// Structured memory breakdown (resident/virtual, and where the OS
+    /// exposes it, private/shared/file-backed), captured at the same point
+    /// as `memory_after_mb`. See `memory_breakdown.rs`.
+    pub memory_breakdown: MemoryBreakdown,
+
     // CPU metrics (SECONDARY)
     // Note: Multiple CPU measurements enable accurate comparison when migrating from
     // Ollama (HTTP-based) to llama.cpp (in-process). The HTTP architecture splits
@@ -73,6 +117,11 @@ pub struct BenchmarkMetrics {
     /// Primary metric for comparing Ollama vs llama.cpp performance.
     pub cpu_total_percent: f64,
 
+    /// Which method produced `cpu_tauri_percent`: exact process-clock
+    /// measurement when available (this crate controls the Tauri process
+    /// directly), sampled otherwise. See `cpu_clock.rs`.
+    pub cpu_tauri_measurement: CpuMeasurement,
+
     // Metadata
     /// Model name used for inference
     pub model_name: String,
@@ -107,6 +156,78 @@ pub struct BenchmarkMetrics {
 
     /// Whether hardware detection failed (metadata may be unreliable)
     pub hardware_detection_failed: bool,
+
+    /// Synthetic CPU capability score, normalized against a reference
+    /// machine (see `hardware::capability`). `None` if hardware detection
+    /// failed.
+    pub cpu_capability_score: Option<f64>,
+    /// Synthetic memory-bandwidth capability score, same conditions as
+    /// `cpu_capability_score`.
+    pub memory_capability_score: Option<f64>,
+
+    // CPU frequency-scaling metadata (SECONDARY)
+    // CPU frequency scaling and turbo boost make timing metrics swing
+    // between runs for reasons unrelated to the model being tested; these
+    // let a reader interpret that variance instead of assuming it's noise.
+    /// Active scaling governor during this test (e.g. "performance"). `None` if unreadable.
+    pub scaling_governor: Option<String>,
+    /// Whether turbo boost was enabled during this test. `None` if the driver doesn't expose it.
+    pub boost_enabled: Option<bool>,
+    /// Base (non-turbo) CPU frequency (kHz), for judging `thermal_throttling_suspected`.
+    pub cpu_base_frequency_khz: Option<u64>,
+    /// True if CPU frequency-scaling metadata isn't available on this host.
+    pub cpu_freq_unsupported: bool,
+
+    // GPU metrics (SECONDARY)
+    // `None` when the run targeted a vendor/platform with no telemetry backend
+    // (e.g. no NVML/ROCm SMI available), mirroring `hardware_detection_failed`.
+    /// Average GPU utilization during inference (%)
+    pub gpu_utilization_percent: Option<f64>,
+
+    /// Average VRAM used by the Ollama process during inference (MB)
+    pub gpu_vram_used_mb: Option<f64>,
+
+    /// Average GPU power draw during inference (W)
+    pub gpu_power_watts: Option<f64>,
+
+    /// Average GPU temperature during inference (°C)
+    pub gpu_temperature_c: Option<f64>,
+
+    /// Average Apple Neural Engine power draw during inference (W), from
+    /// `powermetrics`. `None` off macOS, or without the permissions
+    /// `powermetrics` requires.
+    pub ane_power_watts: Option<f64>,
+
+    /// Average Apple Neural Engine active residency during inference (%),
+    /// same source and caveats as `ane_power_watts`.
+    pub ane_residency_percent: Option<f64>,
+
+    // System-wide memory metrics (SECONDARY)
+    // These track the whole host, unlike `memory_*_mb` above which track only
+    // the Ollama process RSS — critical for telling "the model is slow" apart
+    // from "the host was swapping" on low-spec target machines.
+    /// Total host RAM (MB), constant for the run.
+    pub system_memory_total_mb: f64,
+
+    /// Average host-wide free/available RAM during inference (MB)
+    pub system_memory_avg_free_mb: f64,
+
+    /// Minimum host-wide free/available RAM observed during inference (MB)
+    pub system_memory_min_free_mb: f64,
+
+    /// Average host-wide used RAM during inference (MB)
+    pub system_memory_avg_used_mb: f64,
+
+    /// Average swap used during inference (MB)
+    pub system_swap_avg_used_mb: f64,
+
+    /// Peak swap used observed during inference (MB)
+    pub system_swap_peak_used_mb: f64,
+
+    /// CPU and memory sampled over the generation window, so the UI can plot
+    /// a curve instead of only `memory_before/during/after_mb`'s spot reads.
+    /// Populated from the same background sampler that derives `peak_memory_mb`.
+    pub resource_timeseries: Vec<ResourceSamplePoint>,
 }
 
 /// Summary statistics across multiple benchmark runs
@@ -118,6 +239,16 @@ pub struct BenchmarkSummary {
     pub avg_total_time_ms: f64,
     pub avg_memory_mb: f64,
 
+    /// Autocorrelation-corrected 95% CI on `avg_first_token_ms` (see
+    /// `stats::autocorrelation_corrected_ci`). Naive `std/sqrt(N)` standard
+    /// error understates uncertainty when back-to-back iterations are
+    /// correlated, making two models look "different" when they aren't.
+    pub avg_first_token_ms_ci: (f64, f64),
+    /// Same, for `avg_tokens_per_sec`.
+    pub avg_tokens_per_sec_ci: (f64, f64),
+    /// Same, for `avg_total_time_ms`.
+    pub avg_total_time_ms_ci: (f64, f64),
+
     // CPU summary metrics
     /// Average Ollama/inference process CPU usage (%)
     pub avg_cpu_ollama_percent: f64,
@@ -128,16 +259,130 @@ pub struct BenchmarkSummary {
     /// Average combined CPU usage: ollama + tauri (%)
     pub avg_cpu_total_percent: f64,
 
+    // GPU summary metrics
+    // `None` when no tests in this category reported GPU telemetry.
+    /// Average GPU utilization (%)
+    pub avg_gpu_utilization_percent: Option<f64>,
+    /// Average VRAM used by the Ollama process (MB)
+    pub avg_gpu_vram_used_mb: Option<f64>,
+    /// Average GPU power draw (W)
+    pub avg_gpu_power_watts: Option<f64>,
+    /// Average GPU temperature (°C)
+    pub avg_gpu_temperature_c: Option<f64>,
+
+    /// Average Apple Neural Engine power draw (W)
+    pub avg_ane_power_watts: Option<f64>,
+    /// Average Apple Neural Engine active residency (%)
+    pub avg_ane_residency_percent: Option<f64>,
+
+    /// True if any test in this category observed host-wide free memory drop
+    /// below [`MEMORY_PRESSURE_FREE_THRESHOLD_MB`] mid-run.
+    pub memory_pressure_detected: bool,
+
     pub test_count: usize,
+
+    /// p50/p95/p99/max of this category's first-token latency, read off
+    /// `first_token_histogram`. Reported alongside the arithmetic mean above
+    /// because a fast-on-average model can still spike badly on a minority
+    /// of requests, which `avg_first_token_ms` alone hides.
+    pub first_token_distribution: LatencyDistribution,
+    /// Same, for total response time.
+    pub total_time_distribution: LatencyDistribution,
+
+    /// Raw HDR histogram backing `first_token_distribution`, serialized
+    /// alongside the percentiles so a run's full distribution can be
+    /// re-aggregated later (e.g. merged across runs) without re-benchmarking.
+    pub first_token_histogram: SerializableHistogram,
+    /// Raw HDR histogram backing `total_time_distribution`.
+    pub total_time_histogram: SerializableHistogram,
+}
+
+/// Tail-latency distribution across a "coding session" workload's turns.
+/// Reported separately from `BenchmarkSummary`'s per-category averages
+/// because growing context across turns makes the mean alone misleading -
+/// what a user feels is the slow p95/p99 turn, not the average one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    pub p50_first_token_ms: f64,
+    pub p95_first_token_ms: f64,
+    pub p99_first_token_ms: f64,
+    pub p50_total_response_ms: f64,
+    pub p95_total_response_ms: f64,
+    pub p99_total_response_ms: f64,
+    pub avg_tokens_per_second: f64,
+}
+
+/// Compute [`LatencyPercentiles`] across a set of turns (e.g. the
+/// coding-session workload's `BenchmarkMetrics`, in turn order).
+pub fn calculate_latency_percentiles(turns: &[BenchmarkMetrics]) -> LatencyPercentiles {
+    let first_token: Vec<f64> = turns.iter().map(|m| m.first_token_latency_ms).collect();
+    let total_response: Vec<f64> = turns.iter().map(|m| m.total_response_time_ms).collect();
+    let tokens_per_second: Vec<f64> = turns.iter().map(|m| m.tokens_per_second).collect();
+
+    LatencyPercentiles {
+        p50_first_token_ms: calculate_percentile(&first_token, 50.0),
+        p95_first_token_ms: calculate_percentile(&first_token, 95.0),
+        p99_first_token_ms: calculate_percentile(&first_token, 99.0),
+        p50_total_response_ms: calculate_percentile(&total_response, 50.0),
+        p95_total_response_ms: calculate_percentile(&total_response, 95.0),
+        p99_total_response_ms: calculate_percentile(&total_response, 99.0),
+        avg_tokens_per_second: calculate_average(&tokens_per_second),
+    }
 }
 
+/// Host-wide free memory below this is flagged as memory pressure. Chosen for
+/// the low-spec "SmolPC" target machines this benchmark suite is measuring,
+/// where a low-memory host is the scenario we most need runs to surface.
+pub const MEMORY_PRESSURE_FREE_THRESHOLD_MB: f64 = 500.0;
+
 /// Complete benchmark results including all metrics and summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResults {
     pub metrics: Vec<BenchmarkMetrics>,
+    /// Summary aggregated across every recorded iteration, including warmup.
     pub summary: Vec<BenchmarkSummary>,
+    /// Summary aggregated over iterations after `warmup_iterations`, excluding
+    /// cold-cache/model-load effects from the first iteration(s). Empty if
+    /// every iteration was a warmup iteration.
+    pub steady_state_summary: Vec<BenchmarkSummary>,
+    /// Number of leading iterations excluded from `steady_state_summary`.
+    pub warmup_iterations: usize,
     pub total_duration_seconds: f64,
     pub timestamp: String,
+
+    /// Whether `peak_memory_bytes`/`cpu_time_ms` came from reading the
+    /// Ollama process's cgroup v2 accounting files directly, rather than the
+    /// `false` fallback of having no exact-accounting figures at all (the
+    /// sysinfo-sampled `BenchmarkMetrics` fields are always populated
+    /// regardless). `false` on non-Linux hosts, cgroup v1 hosts, or hosts
+    /// where the accounting files aren't readable.
+    pub cgroup_accounting: bool,
+    /// True peak memory usage across the whole run (bytes), including child
+    /// processes, from the cgroup's `memory.peak`. `None` when
+    /// `cgroup_accounting` is `false`.
+    pub peak_memory_bytes: Option<u64>,
+    /// Exact CPU time consumed across the whole run (ms), from the delta of
+    /// the cgroup's `cpu.stat` `usage_usec` at benchmark start and end.
+    /// `None` when `cgroup_accounting` is `false`.
+    pub cpu_time_ms: Option<f64>,
+
+    /// True if `cpu_governor`'s periodic `scaling_cur_freq` polling observed
+    /// a substantial drop below base frequency at any point in the run,
+    /// suggesting thermal throttling skewed the results. Always `false` when
+    /// frequency metadata is unsupported on this host.
+    pub thermal_throttling_suspected: bool,
+
+    /// Tail-latency distribution across the scripted multi-turn
+    /// "coding session" workload's turns (see `test_suite::CODING_SESSION_TURNS`).
+    /// The turns themselves are included in `metrics` like any other test,
+    /// tagged with `prompt_type == "coding_session"`, so they flow through
+    /// the same CSV export; this is the aggregated p50/p95/p99 view of them.
+    pub coding_session_latency_percentiles: LatencyPercentiles,
+
+    /// Result of the optional sustained-rate load phase (see
+    /// `sustained_load`), run after the iteration-based suite above.
+    /// `None` unless the caller opted in by passing a `SustainedLoadConfig`.
+    pub sustained_load: Option<SustainedLoadResult>,
 }
 
 /// Helper to get current timestamp in ISO 8601 format
@@ -145,6 +390,13 @@ pub fn get_timestamp() -> String {
     chrono::Local::now().to_rfc3339()
 }
 
+/// Average the `Some` values in an optional-metric column, or `None` if every
+/// test in the category lacked telemetry for it (e.g. no GPU backend available).
+fn average_optional(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = values.flatten().fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then_some(sum / count as f64)
+}
+
 /// Calculate summary statistics from a collection of metrics
 pub fn calculate_summary(metrics: &[BenchmarkMetrics]) -> Vec<BenchmarkSummary> {
     let categories = ["short", "medium", "long", "follow-up"];
@@ -161,28 +413,70 @@ pub fn calculate_summary(metrics: &[BenchmarkMetrics]) -> Vec<BenchmarkSummary>
         }
 
         let count = category_metrics.len();
-        let avg_first_token = category_metrics.iter().map(|m| m.first_token_latency_ms).sum::<f64>() / count as f64;
-        let avg_tokens_per_sec = category_metrics.iter().map(|m| m.tokens_per_second).sum::<f64>() / count as f64;
-        let avg_total_time = category_metrics.iter().map(|m| m.total_response_time_ms).sum::<f64>() / count as f64;
+        let first_token_samples: Vec<f64> = category_metrics.iter().map(|m| m.first_token_latency_ms).collect();
+        let tokens_per_sec_samples: Vec<f64> = category_metrics.iter().map(|m| m.tokens_per_second).collect();
+        let total_time_samples: Vec<f64> = category_metrics.iter().map(|m| m.total_response_time_ms).collect();
+
+        let avg_first_token = calculate_average(&first_token_samples);
+        let avg_tokens_per_sec = calculate_average(&tokens_per_sec_samples);
+        let avg_total_time = calculate_average(&total_time_samples);
         let avg_memory = category_metrics.iter().map(|m| m.peak_memory_mb).sum::<f64>() / count as f64;
 
+        let avg_first_token_ms_ci = autocorrelation_corrected_ci(&first_token_samples, SUMMARY_CI_CONFIDENCE);
+        let avg_tokens_per_sec_ci = autocorrelation_corrected_ci(&tokens_per_sec_samples, SUMMARY_CI_CONFIDENCE);
+        let avg_total_time_ms_ci = autocorrelation_corrected_ci(&total_time_samples, SUMMARY_CI_CONFIDENCE);
+
         // Calculate all CPU metrics
         let avg_cpu_ollama = category_metrics.iter().map(|m| m.cpu_ollama_percent).sum::<f64>() / count as f64;
         let avg_cpu_tauri = category_metrics.iter().map(|m| m.cpu_tauri_percent).sum::<f64>() / count as f64;
         let avg_cpu_system = category_metrics.iter().map(|m| m.cpu_system_percent).sum::<f64>() / count as f64;
         let avg_cpu_total = category_metrics.iter().map(|m| m.cpu_total_percent).sum::<f64>() / count as f64;
 
+        let avg_gpu_utilization = average_optional(category_metrics.iter().map(|m| m.gpu_utilization_percent));
+        let avg_gpu_vram = average_optional(category_metrics.iter().map(|m| m.gpu_vram_used_mb));
+        let avg_gpu_power = average_optional(category_metrics.iter().map(|m| m.gpu_power_watts));
+        let avg_gpu_temperature = average_optional(category_metrics.iter().map(|m| m.gpu_temperature_c));
+        let avg_ane_power = average_optional(category_metrics.iter().map(|m| m.ane_power_watts));
+        let avg_ane_residency = average_optional(category_metrics.iter().map(|m| m.ane_residency_percent));
+
+        let memory_pressure_detected = category_metrics
+            .iter()
+            .any(|m| m.system_memory_min_free_mb < MEMORY_PRESSURE_FREE_THRESHOLD_MB);
+
+        let mut first_token_histogram = new_latency_histogram();
+        let mut total_time_histogram = new_latency_histogram();
+        for m in &category_metrics {
+            record_ms(&mut first_token_histogram, m.first_token_latency_ms);
+            record_ms(&mut total_time_histogram, m.total_response_time_ms);
+        }
+        let first_token_distribution = distribution_from_histogram(&first_token_histogram);
+        let total_time_distribution = distribution_from_histogram(&total_time_histogram);
+
         summaries.push(BenchmarkSummary {
             category: category.to_string(),
             avg_first_token_ms: avg_first_token,
             avg_tokens_per_sec,
             avg_total_time_ms: avg_total_time,
             avg_memory_mb: avg_memory,
+            avg_first_token_ms_ci,
+            avg_tokens_per_sec_ci,
+            avg_total_time_ms_ci,
             avg_cpu_ollama_percent: avg_cpu_ollama,
             avg_cpu_tauri_percent: avg_cpu_tauri,
             avg_cpu_system_percent: avg_cpu_system,
-            avg_cpu_total_percent: avg_cpu_total, 
+            avg_cpu_total_percent: avg_cpu_total,
+            avg_gpu_utilization_percent: avg_gpu_utilization,
+            avg_gpu_vram_used_mb: avg_gpu_vram,
+            avg_gpu_power_watts: avg_gpu_power,
+            avg_gpu_temperature_c: avg_gpu_temperature,
+            avg_ane_power_watts: avg_ane_power,
+            avg_ane_residency_percent: avg_ane_residency,
+            memory_pressure_detected,
             test_count: count,
+            first_token_distribution,
+            total_time_distribution,
+            first_token_histogram: SerializableHistogram(first_token_histogram),
+            total_time_histogram: SerializableHistogram(total_time_histogram),
         });
     }
 
@@ -207,16 +501,25 @@ mod tests {
             total_response_time_ms: total_time,
             tokens_per_second: tokens_per_sec,
             avg_token_latency_ms: total_time / 100.0, // Simplified
+            final_token_ewma_tokens_per_sec: Some(tokens_per_sec),
             timing_source: TimingSource::Native,
             memory_before_mb: 1000.0,
             memory_during_mb: 1100.0,
             memory_after_mb: 1000.0,
             peak_memory_mb: peak_memory,
+            memory_breakdown: MemoryBreakdown {
+                resident_mb: 1000.0,
+                virtual_mb: 2000.0,
+                private_mb: Some(200.0),
+                shared_mb: Some(50.0),
+                file_backed_mb: Some(750.0),
+            },
             // New CPU metrics - using cpu as ollama, simulating typical values
             cpu_ollama_percent: cpu,
             cpu_tauri_percent: cpu * 0.4, // Simulate ~40% of ollama's CPU for HTTP overhead
             cpu_system_percent: cpu * 1.5, // Simulate system-wide being higher
             cpu_total_percent: cpu + (cpu * 0.4), // ollama + tauri
+            cpu_tauri_measurement: CpuMeasurement::Sampled,
             model_name: "test-model".to_string(),
             prompt_type: category.to_string(),
             prompt: "test prompt".to_string(),
@@ -228,6 +531,25 @@ mod tests {
             avx2_supported: true,
             npu_detected: false,
             hardware_detection_failed: false,
+            cpu_capability_score: Some(1.0),
+            memory_capability_score: Some(1.0),
+            scaling_governor: Some("performance".to_string()),
+            boost_enabled: Some(true),
+            cpu_base_frequency_khz: Some(3_000_000),
+            cpu_freq_unsupported: false,
+            gpu_utilization_percent: Some(cpu * 1.2), // Simulate plausible GPU load
+            gpu_vram_used_mb: Some(peak_memory * 2.0),
+            gpu_power_watts: Some(cpu * 3.0),
+            gpu_temperature_c: Some(60.0),
+            ane_power_watts: None,
+            ane_residency_percent: None,
+            system_memory_total_mb: 8192.0,
+            system_memory_avg_free_mb: 4096.0,
+            system_memory_min_free_mb: 3000.0,
+            system_memory_avg_used_mb: 4096.0,
+            system_swap_avg_used_mb: 0.0,
+            system_swap_peak_used_mb: 0.0,
+            resource_timeseries: Vec::new(),
         }
     }
 
@@ -315,6 +637,122 @@ mod tests {
         assert!((summary[0].avg_cpu_total_percent - 77.7).abs() < 0.01);
     }
 
+    #[test]
+    fn test_calculate_summary_gpu_telemetry_averaged() {
+        let metrics = vec![
+            create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0),
+            create_test_metric("short", 200.0, 20.0, 2000.0, 600.0, 60.0),
+        ];
+
+        let summary = calculate_summary(&metrics);
+
+        assert_eq!(summary[0].avg_gpu_utilization_percent, Some(66.0)); // (60.0 + 72.0) / 2
+        assert_eq!(summary[0].avg_gpu_temperature_c, Some(60.0));
+    }
+
+    #[test]
+    fn test_calculate_summary_gpu_telemetry_missing_is_none() {
+        let metrics = vec![BenchmarkMetrics {
+            gpu_utilization_percent: None,
+            gpu_vram_used_mb: None,
+            gpu_power_watts: None,
+            gpu_temperature_c: None,
+            ..create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0)
+        }];
+
+        let summary = calculate_summary(&metrics);
+
+        assert_eq!(summary[0].avg_gpu_utilization_percent, None);
+        assert_eq!(summary[0].avg_gpu_power_watts, None);
+    }
+
+    #[test]
+    fn test_calculate_summary_ane_telemetry_averaged() {
+        let metrics = vec![
+            BenchmarkMetrics {
+                ane_power_watts: Some(2.0),
+                ane_residency_percent: Some(10.0),
+                ..create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0)
+            },
+            BenchmarkMetrics {
+                ane_power_watts: Some(4.0),
+                ane_residency_percent: Some(20.0),
+                ..create_test_metric("short", 200.0, 20.0, 2000.0, 600.0, 60.0)
+            },
+        ];
+
+        let summary = calculate_summary(&metrics);
+
+        assert_eq!(summary[0].avg_ane_power_watts, Some(3.0));
+        assert_eq!(summary[0].avg_ane_residency_percent, Some(15.0));
+    }
+
+    #[test]
+    fn test_calculate_summary_ane_telemetry_missing_is_none() {
+        let metrics = vec![create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0)];
+
+        let summary = calculate_summary(&metrics);
+
+        assert_eq!(summary[0].avg_ane_power_watts, None);
+        assert_eq!(summary[0].avg_ane_residency_percent, None);
+    }
+
+    #[test]
+    fn test_calculate_summary_memory_pressure_not_detected() {
+        let metrics = vec![create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0)];
+
+        let summary = calculate_summary(&metrics);
+
+        assert!(!summary[0].memory_pressure_detected);
+    }
+
+    #[test]
+    fn test_calculate_summary_memory_pressure_detected() {
+        let metrics = vec![BenchmarkMetrics {
+            system_memory_min_free_mb: 200.0, // below MEMORY_PRESSURE_FREE_THRESHOLD_MB
+            ..create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0)
+        }];
+
+        let summary = calculate_summary(&metrics);
+
+        assert!(summary[0].memory_pressure_detected);
+    }
+
+    #[test]
+    fn test_calculate_summary_ci_brackets_the_average() {
+        let metrics = vec![
+            create_test_metric("short", 100.0, 10.0, 1000.0, 500.0, 50.0),
+            create_test_metric("short", 200.0, 20.0, 2000.0, 600.0, 60.0),
+            create_test_metric("short", 150.0, 15.0, 1500.0, 550.0, 55.0),
+            create_test_metric("short", 120.0, 12.0, 1200.0, 520.0, 52.0),
+        ];
+
+        let summary = calculate_summary(&metrics);
+
+        let (lower, upper) = summary[0].avg_first_token_ms_ci;
+        assert!(lower <= summary[0].avg_first_token_ms);
+        assert!(upper >= summary[0].avg_first_token_ms);
+    }
+
+    #[test]
+    fn test_calculate_summary_latency_distribution_percentiles() {
+        let metrics: Vec<_> = (1..=100)
+            .map(|ms| create_test_metric("short", ms as f64, 10.0, ms as f64 * 10.0, 500.0, 50.0))
+            .collect();
+
+        let summary = calculate_summary(&metrics);
+
+        let first_token = summary[0].first_token_distribution;
+        assert!((first_token.p50_ms - 50.0).abs() < 1.0);
+        assert!((first_token.p99_ms - 99.0).abs() < 1.0);
+        // max() reports the highest value equivalent to the largest sample at
+        // the histogram's 3 significant digits.
+        assert_eq!(first_token.max_ms, 100.031);
+
+        let total_time = summary[0].total_time_distribution;
+        assert_eq!(total_time.max_ms, 1000.447);
+    }
+
     #[test]
     fn test_get_timestamp_format() {
         let timestamp = get_timestamp();
@@ -1,11 +1,28 @@
+mod cgroup;
+pub mod compare;
+pub mod constrained;
+mod cpu_clock;
+mod cpu_governor;
 pub mod export;
+pub(crate) mod gpu;
+mod histogram;
+mod memory_breakdown;
 pub mod metrics;
 mod process;
 pub mod runner;
 mod sampling;
+mod stats;
+pub mod sustained_load;
 pub mod test_suite;
+mod token_ewma;
 
-pub use export::{create_readme, export_to_csv, get_benchmarks_dir_with_app_handle};
+pub use compare::{compare_benchmark_csvs, render_comparison_markdown, ComparisonResult, ComparisonVerdict};
+pub use constrained::{run_constrained_benchmark, ConstrainedBenchmarkOutcome, ConstrainedBenchmarkResults, ResourceEnvelope};
+pub use export::{
+    create_readme, export_results, export_summary_csv, export_to_csv, export_to_json,
+    get_benchmarks_dir_with_app_handle, ExportFormat,
+};
+pub use sustained_load::{run_sustained_load, SustainedLoadConfig, SustainedLoadResult};
 
 // Keep get_benchmarks_dir for tests
 #[allow(unused_imports)]
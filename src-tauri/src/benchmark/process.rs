@@ -4,6 +4,8 @@
 
 use crate::commands::ollama::{OllamaConfig, OllamaMessage, OllamaRequest};
 use crate::hardware;
+use crate::hardware::types::GpuVendor;
+use super::cpu_governor::CpuFrequencyState;
 use super::test_suite::SHORT_PROMPTS;
 use sysinfo::System;
 use std::time::Duration;
@@ -21,33 +23,79 @@ const WARMUP_STABILIZATION_DELAY: Duration = Duration::from_millis(500);
 pub struct HardwareSnapshot {
     pub cpu_model: String,
     pub gpu_name: String,
+    /// Vendor of the primary GPU, used to pick a GPU telemetry backend
+    /// (NVML for NVIDIA, ROCm SMI for AMD) during resource sampling.
+    pub gpu_vendor: GpuVendor,
     pub avx2_supported: bool,
     pub npu_detected: bool,
     pub detection_failed: bool,
+
+    /// Synthetic CPU capability score from the reference-normalized
+    /// micro-benchmark (see `hardware::capability`). `None` if hardware
+    /// detection failed.
+    pub cpu_capability_score: Option<f64>,
+    /// Synthetic memory-bandwidth capability score, same conditions as
+    /// `cpu_capability_score`.
+    pub memory_capability_score: Option<f64>,
+
+    // CPU frequency-scaling metadata (see `cpu_governor`), flattened here and
+    // into every `BenchmarkMetrics` row, matching `cpu_model`/`gpu_name` above.
+    /// Active scaling governor (e.g. "performance", "powersave"). `None` if unreadable.
+    pub scaling_governor: Option<String>,
+    /// Whether turbo boost is enabled. `None` if the driver doesn't expose a boost toggle.
+    pub boost_enabled: Option<bool>,
+    /// Base (non-turbo) CPU frequency in kHz, used to judge thermal throttling mid-run.
+    pub base_frequency_khz: Option<u64>,
+    /// True when CPU frequency-scaling metadata isn't available on this host
+    /// (non-Linux, or no cpufreq driver) - distinguishes "not supported" from
+    /// "happened to read as None".
+    pub cpu_freq_unsupported: bool,
 }
 
 impl HardwareSnapshot {
     /// Detect hardware, falling back to defaults on failure.
     pub async fn detect() -> Self {
-        match hardware::detect_all().await {
+        let freq = CpuFrequencyState::detect();
+
+        // Use the scored detection path: a benchmark suite run already takes
+        // seconds, so paying the micro-benchmark's wall-clock budget once
+        // here is worth having the capability score in every exported row.
+        match hardware::detect_all_with_score().await {
             Ok(info) => {
-                let gpu_name = info.gpus.iter()
+                let primary_gpu = info.gpus.iter()
                     .find(|g| g.device_type.eq_ignore_ascii_case("discrete"))
-                    .or(info.gpus.first())
+                    .or(info.gpus.first());
+                let gpu_name = primary_gpu
                     .map(|g| g.name.clone())
                     .unwrap_or_else(|| "No GPU".to_string());
+                let gpu_vendor = primary_gpu
+                    .map(|g| g.vendor.clone())
+                    .unwrap_or(GpuVendor::Unknown);
 
                 Self {
                     cpu_model: info.cpu.brand.clone(),
                     gpu_name,
+                    gpu_vendor,
                     avx2_supported: info.cpu.features.avx2,
                     npu_detected: info.npu.as_ref().is_some_and(|n| n.detected),
                     detection_failed: false,
+                    scaling_governor: freq.scaling_governor,
+                    boost_enabled: freq.boost_enabled,
+                    base_frequency_khz: freq.base_frequency_khz,
+                    cpu_freq_unsupported: freq.unsupported,
+                    cpu_capability_score: info.capability_score.map(|s| s.cpu_score),
+                    memory_capability_score: info.capability_score.map(|s| s.memory_score),
                 }
             }
             Err(e) => {
                 log::warn!("Hardware detection failed: {e}");
-                Self::default()
+                Self {
+                    scaling_governor: freq.scaling_governor,
+                    boost_enabled: freq.boost_enabled,
+                    base_frequency_khz: freq.base_frequency_khz,
+                    cpu_freq_unsupported: freq.unsupported,
+                    ..Self::default()
+                }
             }
         }
     }
@@ -58,9 +106,16 @@ impl Default for HardwareSnapshot {
         Self {
             cpu_model: "Unknown CPU".to_string(),
             gpu_name: "Unknown GPU".to_string(),
+            gpu_vendor: GpuVendor::Unknown,
             avx2_supported: false,
             npu_detected: false,
             detection_failed: true,
+            scaling_governor: None,
+            boost_enabled: None,
+            base_frequency_khz: None,
+            cpu_freq_unsupported: true,
+            cpu_capability_score: None,
+            memory_capability_score: None,
         }
     }
 }
@@ -84,7 +139,7 @@ pub async fn warmup_and_find_process(
         stream: false,
     };
 
-    let url = format!("{}/api/chat", config.base_url());
+    let url = format!("{}/api/chat", config.base_url()?);
 
     let response = client
         .post(&url)
@@ -3,11 +3,18 @@
 //! Runs inference tests against Ollama and collects timing/resource metrics.
 //! Uses Ollama's native nanosecond-precision timing data when available.
 
-use super::metrics::{BenchmarkMetrics, BenchmarkResults, TimingSource, calculate_summary, get_timestamp};
+use super::cgroup::CgroupHandle;
+use super::cpu_clock::{CpuClockSnapshot, elapsed_cpu_percent};
+use super::cpu_governor::{is_throttled, CpuFrequencyState, StabilizeGuard};
+use super::memory_breakdown::MemoryBreakdown;
+use super::metrics::{BenchmarkMetrics, BenchmarkResults, CpuMeasurement, TimingSource, calculate_latency_percentiles, calculate_summary, get_timestamp};
 use super::process::{HardwareSnapshot, warmup_and_find_process};
-use super::sampling::{SamplingState, collect_sampling_results, spawn_resource_sampler, calculate_average, calculate_median};
-use super::test_suite::{get_test_suite, get_total_test_count, PromptCategory, SHORT_PROMPTS};
+use super::sampling::{SamplingState, collect_sampling_results, spawn_resource_sampler, calculate_average, calculate_median, calculate_min, calculate_max, average_if_present};
+use super::sustained_load::{run_sustained_load, SustainedLoadConfig};
+use super::test_suite::{get_test_suite, get_total_test_count, PromptCategory, CODING_SESSION_TURNS, SHORT_PROMPTS};
+use super::token_ewma::TokenLatencyEwma;
 use crate::commands::ollama::{OllamaConfig, OllamaMessage, OllamaRequest, OllamaResponse};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use sysinfo::System;
@@ -20,6 +27,9 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
 const TEST_STABILIZATION_DELAY: Duration = Duration::from_millis(500);
 const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
 
+/// Smoothing factor for the live per-token-latency EWMA (see `token_ewma.rs`).
+const TOKEN_EWMA_ALPHA: f64 = 0.3;
+
 /// ID of the test used for follow-up context.
 const CONTEXT_SOURCE_TEST_ID: &str = "short_1";
 
@@ -62,7 +72,12 @@ struct TimingMetrics {
 }
 
 /// Extract timing metrics from Ollama's native data, falling back to client-side if unavailable.
-fn calculate_timing_metrics(response: &OllamaResponse, client_elapsed_ms: f64) -> TimingMetrics {
+///
+/// `full_content` is the accumulated response text: with streaming requests,
+/// `response`'s own `message` is the final (`done: true`) chunk, which
+/// carries no content of its own, so the client-side token estimate needs
+/// the caller's separately-accumulated content instead.
+fn calculate_timing_metrics(response: &OllamaResponse, full_content: &str, client_elapsed_ms: f64) -> TimingMetrics {
     // Prefer native Ollama timing (nanosecond precision)
     if let (Some(eval_count), Some(eval_duration_ns), Some(total_duration_ns)) = (
         response.eval_count,
@@ -99,8 +114,7 @@ fn calculate_timing_metrics(response: &OllamaResponse, client_elapsed_ms: f64) -
     // Fallback: estimate from client-side measurements
     log::warn!("Ollama did not provide native timing data, using client-side estimates");
 
-    let response_content = response.message.as_ref().map_or("", |m| m.content.as_str());
-    let estimated_tokens = (response_content.len() / 4).max(1); // ~4 chars per token
+    let estimated_tokens = (full_content.len() / 4).max(1); // ~4 chars per token
 
     let tokens_per_second = if client_elapsed_ms > 0.0 {
         (estimated_tokens as f64) / (client_elapsed_ms / 1000.0)
@@ -118,6 +132,61 @@ fn calculate_timing_metrics(response: &OllamaResponse, client_elapsed_ms: f64) -
     }
 }
 
+// =============================================================================
+// Streaming Response Consumption
+// =============================================================================
+
+/// Consume a streaming `/api/chat` response, feeding a [`TokenLatencyEwma`] a
+/// timestamp per token as it arrives - calling `on_token_speed` with the
+/// live tokens/sec estimate so the frontend can show a "current speed"
+/// indicator - and accumulating the full response content. Returns the
+/// final (`done: true`) message, which carries Ollama's timing/token-count
+/// metadata, the accumulated content, and the EWMA's last estimate.
+async fn consume_chat_stream(
+    response: reqwest::Response,
+    on_token_speed: &impl Fn(f64),
+) -> Result<(OllamaResponse, String, Option<f64>), String> {
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let mut content = String::new();
+    let mut ewma = TokenLatencyEwma::new(TOKEN_EWMA_ALPHA);
+    let mut final_response: Option<OllamaResponse> = None;
+
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|e| format!("Stream error: {e}"))?;
+        buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim().to_string();
+            buffer.drain(..=pos);
+            if line.is_empty() {
+                continue;
+            }
+
+            let parsed: OllamaResponse = serde_json::from_str(&line)
+                .map_err(|e| format!("Failed to parse streamed response: {e}"))?;
+
+            if let Some(message) = &parsed.message {
+                if !message.content.is_empty() {
+                    content.push_str(&message.content);
+                    if let Some(sample) = ewma.record_token(Instant::now()) {
+                        on_token_speed(sample.tokens_per_sec);
+                    }
+                }
+            }
+
+            if parsed.done {
+                final_response = Some(parsed);
+            }
+        }
+    }
+
+    let final_response = final_response
+        .ok_or_else(|| "Ollama chat stream ended without a final message".to_string())?;
+    let final_tokens_per_sec = ewma.current().map(|sample| sample.tokens_per_sec);
+    Ok((final_response, content, final_tokens_per_sec))
+}
+
 // =============================================================================
 // Request Building
 // =============================================================================
@@ -163,6 +232,7 @@ async fn run_single_test(
     config: &OllamaConfig,
     ollama_pid: sysinfo::Pid,
     hardware: &HardwareSnapshot,
+    on_token_speed: &impl Fn(f64),
 ) -> Result<(BenchmarkMetrics, String), String> {
     let mut sys = System::new_all();
     sys.refresh_all();
@@ -175,16 +245,17 @@ async fn run_single_test(
     let request = OllamaRequest {
         model: model.clone(),
         messages: build_request_messages(&prompt, context),
-        stream: false,
+        stream: true,
     };
 
     // Start background resource sampling
     let sampling_state = SamplingState::new(memory_before_mb);
-    let sampling_done = spawn_resource_sampler(ollama_pid, sampling_state.clone());
+    let sampling_done = spawn_resource_sampler(ollama_pid, sampling_state.clone(), hardware.gpu_vendor.clone());
 
     // Execute request
-    let url = format!("{}/api/chat", config.base_url());
+    let url = format!("{}/api/chat", config.base_url()?);
     let request_start = Instant::now();
+    let cpu_clock_start = CpuClockSnapshot::capture();
 
     let response = client
         .post(&url)
@@ -210,22 +281,17 @@ async fn run_single_test(
         ));
     }
 
-    let ollama_response: OllamaResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {e}"))?;
+    let (ollama_response, response_content, final_token_ewma_tokens_per_sec) =
+        consume_chat_stream(response, on_token_speed).await?;
 
-    let client_elapsed_ms = request_start.elapsed().as_secs_f64() * 1000.0;
+    let wall_clock_elapsed = request_start.elapsed();
+    let client_elapsed_ms = wall_clock_elapsed.as_secs_f64() * 1000.0;
+    let cpu_clock_end = CpuClockSnapshot::capture();
 
     // Collect sampling results
     let sampling_results = collect_sampling_results(sampling_state, sampling_done, ollama_pid).await?;
 
-    let timing = calculate_timing_metrics(&ollama_response, client_elapsed_ms);
-
-    let response_content = ollama_response.message
-        .as_ref()
-        .map(|m| m.content.clone())
-        .unwrap_or_default();
+    let timing = calculate_timing_metrics(&ollama_response, &response_content, client_elapsed_ms);
 
     // Get final memory state
     sys.refresh_all();
@@ -233,12 +299,37 @@ async fn run_single_test(
         .process(ollama_pid)
         .map(|p| (p.memory() as f64) / BYTES_PER_MB)
         .ok_or_else(|| format!("Ollama process (PID {ollama_pid}) disappeared"))?;
+    let memory_breakdown = MemoryBreakdown::capture(ollama_pid, &sys)
+        .ok_or_else(|| format!("Ollama process (PID {ollama_pid}) disappeared"))?;
 
     // Calculate resource metrics
     let avg_cpu_ollama = calculate_average(&sampling_results.cpu_ollama_samples);
     let avg_cpu_tauri = calculate_average(&sampling_results.cpu_tauri_samples);
     let avg_cpu_system = calculate_average(&sampling_results.cpu_system_samples);
     let median_memory_during = calculate_median(&sampling_results.memory_samples);
+    let gpu_utilization_percent = average_if_present(&sampling_results.gpu_utilization_samples);
+    let gpu_vram_used_mb = average_if_present(&sampling_results.gpu_vram_samples);
+    let gpu_power_watts = average_if_present(&sampling_results.gpu_power_samples);
+    let gpu_temperature_c = average_if_present(&sampling_results.gpu_temperature_samples);
+    let ane_power_watts = average_if_present(&sampling_results.ane_power_samples);
+    let ane_residency_percent = average_if_present(&sampling_results.ane_residency_samples);
+    let system_memory_avg_free_mb = calculate_average(&sampling_results.system_free_samples);
+    let system_memory_min_free_mb = calculate_min(&sampling_results.system_free_samples);
+    let system_memory_avg_used_mb = calculate_average(&sampling_results.system_used_samples);
+    let system_swap_avg_used_mb = calculate_average(&sampling_results.swap_used_samples);
+    let system_swap_peak_used_mb = calculate_max(&sampling_results.swap_used_samples);
+
+    // Prefer the exact process CPU clock for the Tauri side (this crate
+    // controls its own process); Ollama runs separately and is only ever
+    // available via sampling.
+    let cpu_clock_exact = match (&cpu_clock_start, &cpu_clock_end) {
+        (Some(start), Some(end)) => elapsed_cpu_percent(start, end, wall_clock_elapsed),
+        _ => None,
+    };
+    let (cpu_tauri_percent, cpu_tauri_measurement) = match cpu_clock_exact {
+        Some(exact) => (exact, CpuMeasurement::ProcessClock),
+        None => (avg_cpu_tauri, CpuMeasurement::Sampled),
+    };
 
     #[allow(deprecated)]
     Ok((
@@ -247,15 +338,18 @@ async fn run_single_test(
             total_response_time_ms: timing.total_response_time_ms,
             tokens_per_second: timing.tokens_per_second,
             avg_token_latency_ms: timing.avg_token_latency_ms,
+            final_token_ewma_tokens_per_sec,
             timing_source: timing.timing_source,
             memory_before_mb,
             memory_during_mb: median_memory_during,
             memory_after_mb,
             peak_memory_mb: sampling_results.peak_memory_mb,
+            memory_breakdown,
             cpu_ollama_percent: avg_cpu_ollama,
-            cpu_tauri_percent: avg_cpu_tauri,
+            cpu_tauri_percent,
+            cpu_tauri_measurement,
             cpu_system_percent: avg_cpu_system,
-            cpu_total_percent: avg_cpu_ollama + avg_cpu_tauri,
+            cpu_total_percent: avg_cpu_ollama + cpu_tauri_percent,
             model_name: model,
             prompt_type: category.as_str().to_string(),
             prompt,
@@ -267,22 +361,109 @@ async fn run_single_test(
             avx2_supported: hardware.avx2_supported,
             npu_detected: hardware.npu_detected,
             hardware_detection_failed: hardware.detection_failed,
+            cpu_capability_score: hardware.cpu_capability_score,
+            memory_capability_score: hardware.memory_capability_score,
+            scaling_governor: hardware.scaling_governor.clone(),
+            boost_enabled: hardware.boost_enabled,
+            cpu_base_frequency_khz: hardware.base_frequency_khz,
+            cpu_freq_unsupported: hardware.cpu_freq_unsupported,
+            gpu_utilization_percent,
+            gpu_vram_used_mb,
+            gpu_power_watts,
+            gpu_temperature_c,
+            ane_power_watts,
+            ane_residency_percent,
+            system_memory_total_mb: sampling_results.system_memory_total_mb,
+            system_memory_avg_free_mb,
+            system_memory_min_free_mb,
+            system_memory_avg_used_mb,
+            system_swap_avg_used_mb,
+            system_swap_peak_used_mb,
+            resource_timeseries: sampling_results.resource_timeseries,
         },
         response_content,
     ))
 }
 
+// =============================================================================
+// Coding Session Workload
+// =============================================================================
+
+/// Replay `CODING_SESSION_TURNS` as a single growing conversation, each turn
+/// sent with the full history so far (like `build_followup_context`, but
+/// accumulating across every turn instead of just one prior exchange).
+/// Unlike the isolated prompt categories, context length - and therefore
+/// prompt-eval cost - increases turn over turn, the way it does in real use.
+async fn run_coding_session(
+    model: &str,
+    client: &reqwest::Client,
+    config: &OllamaConfig,
+    ollama_pid: sysinfo::Pid,
+    hardware: &HardwareSnapshot,
+    progress_callback: &impl Fn(BenchmarkProgress),
+    on_token_speed: &impl Fn(f64),
+    current_test: &mut usize,
+    total_tests: usize,
+) -> Result<Vec<BenchmarkMetrics>, String> {
+    let mut turn_metrics = Vec::with_capacity(CODING_SESSION_TURNS.len());
+    let mut history: Vec<OllamaMessage> = Vec::new();
+
+    for (turn_index, prompt) in CODING_SESSION_TURNS.iter().enumerate() {
+        *current_test += 1;
+
+        progress_callback(BenchmarkProgress {
+            current: *current_test,
+            total: total_tests,
+            current_test: format!("coding_session_{} (turn {})", turn_index + 1, turn_index + 1),
+            iteration: turn_index + 1,
+        });
+
+        let context = (!history.is_empty()).then(|| history.clone());
+
+        let (metrics, response_content) = run_single_test(
+            prompt.to_string(),
+            PromptCategory::CodingSession,
+            model.to_string(),
+            turn_index + 1,
+            context,
+            client,
+            config,
+            ollama_pid,
+            hardware,
+            on_token_speed,
+        ).await?;
+
+        history.push(OllamaMessage { role: "user".to_string(), content: prompt.to_string() });
+        history.push(OllamaMessage { role: "assistant".to_string(), content: response_content });
+
+        turn_metrics.push(metrics);
+
+        tokio::time::sleep(TEST_STABILIZATION_DELAY).await;
+    }
+
+    Ok(turn_metrics)
+}
+
 // =============================================================================
 // Benchmark Suite Execution
 // =============================================================================
 
 /// Run the complete benchmark suite against a model.
+///
+/// `warmup_iterations` leading iterations are still run and recorded in
+/// `metrics`/`summary` for transparency, but are excluded from
+/// `steady_state_summary` so cold-cache/model-load effects in the first
+/// iteration(s) don't skew the reported tokens/sec and latency.
 pub async fn run_benchmark_suite(
     model: String,
     iterations: usize,
+    warmup_iterations: usize,
+    stabilize_cpu: bool,
     client: &reqwest::Client,
     config: &OllamaConfig,
+    sustained_load_config: Option<SustainedLoadConfig>,
     progress_callback: impl Fn(BenchmarkProgress),
+    on_token_speed: impl Fn(f64),
 ) -> Result<BenchmarkResults, String> {
     let hardware = HardwareSnapshot::detect().await;
 
@@ -290,14 +471,35 @@ pub async fn run_benchmark_suite(
         log::warn!("Hardware detection failed - benchmark metadata may be incomplete");
     }
 
+    // `_stabilize_guard` is never read again, but must stay alive (and
+    // therefore bound to a name, not `_`) until the suite finishes: its
+    // `Drop` impl is the teardown guard that restores the prior governor/
+    // boost state, and runs even if the suite below panics or is cancelled.
+    let _stabilize_guard = stabilize_cpu.then(StabilizeGuard::stabilize).flatten();
+    if stabilize_cpu && _stabilize_guard.is_none() {
+        log::warn!("Requested CPU stabilization but couldn't write cpufreq sysfs - running unstabilized");
+    }
+
     let ollama_pid = warmup_and_find_process(&model, client, config).await?;
 
+    // Resolved once per run: cgroup v2 accounting files give exact CPU-time
+    // and peak-memory figures (including child processes) that the sysinfo
+    // polling in `sampling.rs` can only approximate. Falls back to `None`
+    // transparently on cgroup v1 hosts or non-Linux platforms.
+    let cgroup = CgroupHandle::resolve(ollama_pid);
+    let cgroup_accounting = cgroup.is_some();
+    if !cgroup_accounting {
+        log::debug!("cgroup v2 accounting unavailable for PID {ollama_pid}, reporting sysinfo-sampled metrics only");
+    }
+    let cpu_usage_usec_start = cgroup.as_ref().and_then(CgroupHandle::read_cpu_usage_usec);
+
     let suite_start = Instant::now();
     let test_suite = get_test_suite();
-    let total_tests = get_total_test_count(iterations);
+    let total_tests = get_total_test_count(&test_suite, iterations);
     let mut all_metrics = Vec::new();
     let mut current_test = 0;
     let mut last_response: Option<String> = None;
+    let mut thermal_throttling_suspected = false;
 
     for iteration in 1..=iterations {
         for test in &test_suite {
@@ -326,6 +528,7 @@ pub async fn run_benchmark_suite(
                 config,
                 ollama_pid,
                 &hardware,
+                &on_token_speed,
             ).await?;
 
             // Store first short test response for follow-up context
@@ -335,16 +538,75 @@ pub async fn run_benchmark_suite(
 
             all_metrics.push(metrics);
 
+            // Poll `scaling_cur_freq` once per test as a cheap periodic check
+            // for thermal throttling across the run's duration.
+            if let Some(base_khz) = hardware.base_frequency_khz {
+                if let Some(current_khz) = CpuFrequencyState::read_current_frequency_khz() {
+                    if is_throttled(current_khz, base_khz) {
+                        thermal_throttling_suspected = true;
+                    }
+                }
+            }
+
             tokio::time::sleep(TEST_STABILIZATION_DELAY).await;
         }
     }
 
+    let coding_session_turns = run_coding_session(
+        &model,
+        client,
+        config,
+        ollama_pid,
+        &hardware,
+        &progress_callback,
+        &on_token_speed,
+        &mut current_test,
+        total_tests,
+    ).await?;
+    let coding_session_latency_percentiles = calculate_latency_percentiles(&coding_session_turns);
+    all_metrics.extend(coding_session_turns);
+
     let summary = calculate_summary(&all_metrics);
 
+    let steady_state_metrics: Vec<_> = all_metrics
+        .iter()
+        .filter(|m| m.iteration > warmup_iterations)
+        .cloned()
+        .collect();
+    let steady_state_summary = calculate_summary(&steady_state_metrics);
+
+    let peak_memory_bytes = cgroup.as_ref().and_then(CgroupHandle::read_memory_peak);
+    let cpu_time_ms = cpu_usage_usec_start.and_then(|start| {
+        cgroup
+            .as_ref()
+            .and_then(CgroupHandle::read_cpu_usage_usec)
+            .map(|end| end.saturating_sub(start) as f64 / 1000.0)
+    });
+
+    let sustained_load = if let Some(load_config) = sustained_load_config {
+        match run_sustained_load(client, config, &load_config).await {
+            Ok(result) => Some(result),
+            Err(e) => {
+                log::warn!("Sustained-load run failed: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     Ok(BenchmarkResults {
         metrics: all_metrics,
         summary,
+        steady_state_summary,
+        warmup_iterations,
         total_duration_seconds: suite_start.elapsed().as_secs_f64(),
         timestamp: get_timestamp(),
+        cgroup_accounting,
+        peak_memory_bytes,
+        cpu_time_ms,
+        thermal_throttling_suspected,
+        coding_session_latency_percentiles,
+        sustained_load,
     })
 }
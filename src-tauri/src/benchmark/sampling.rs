@@ -3,7 +3,11 @@
 //! Provides background CPU and memory monitoring during inference.
 //! Uses `std::sync::Mutex` (not tokio) since locks aren't held across `.await` points.
 
+use super::gpu::GpuSampler;
+use crate::hardware::types::GpuVendor;
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use sysinfo::System;
 
 /// Bytes to megabytes conversion factor.
@@ -15,6 +19,42 @@ const SAMPLING_INTERVAL: std::time::Duration = std::time::Duration::from_millis(
 /// CPU baseline delay required by sysinfo (needs two refresh cycles).
 const CPU_BASELINE_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
 
+/// Host-wide memory snapshot for a single sampling tick.
+///
+/// `sysinfo` exposes total/free/available/used memory and swap portably, but
+/// not the wired/non-evictable vs. cached/buffer split (that's Linux- and
+/// macOS-specific and not part of its public API), so that breakdown isn't
+/// collected here.
+#[derive(Debug, Clone, Copy)]
+struct SystemMemorySample {
+    total_mb: f64,
+    free_mb: f64,
+    used_mb: f64,
+    swap_used_mb: f64,
+}
+
+impl SystemMemorySample {
+    fn from_system(sys: &System) -> Self {
+        Self {
+            total_mb: (sys.total_memory() as f64) / BYTES_PER_MB,
+            free_mb: (sys.available_memory() as f64) / BYTES_PER_MB,
+            used_mb: (sys.used_memory() as f64) / BYTES_PER_MB,
+            swap_used_mb: (sys.used_swap() as f64) / BYTES_PER_MB,
+        }
+    }
+}
+
+/// One point on the resource-usage curve over a test's duration, timestamped
+/// relative to the sampler's start so the UI can plot CPU/memory against the
+/// generation window instead of only before/during/after/peak spot figures.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ResourceSamplePoint {
+    pub elapsed_ms: f64,
+    pub cpu_ollama_percent: f64,
+    pub cpu_tauri_percent: f64,
+    pub memory_mb: f64,
+}
+
 /// Collected resource samples from a benchmark run.
 #[derive(Debug)]
 pub struct SamplingResults {
@@ -22,7 +62,26 @@ pub struct SamplingResults {
     pub cpu_tauri_samples: Vec<f64>,
     pub cpu_system_samples: Vec<f64>,
     pub memory_samples: Vec<f64>,
+    /// The same data as the `*_samples` vectors above, zipped with elapsed
+    /// time since sampling started, for plotting the curve over the window.
+    pub resource_timeseries: Vec<ResourceSamplePoint>,
     pub peak_memory_mb: f64,
+    // GPU sample vectors only contain entries for ticks where the backend
+    // (NVML/ROCm SMI/powermetrics) actually reported a value, so they may be
+    // shorter than the CPU/memory vectors or empty entirely on
+    // GPU-telemetry-less machines. The `ane_*` vectors are Apple-Neural-Engine
+    // specific and stay empty off macOS or without `powermetrics` permissions.
+    pub gpu_utilization_samples: Vec<f64>,
+    pub gpu_vram_samples: Vec<f64>,
+    pub gpu_power_samples: Vec<f64>,
+    pub gpu_temperature_samples: Vec<f64>,
+    pub ane_power_samples: Vec<f64>,
+    pub ane_residency_samples: Vec<f64>,
+    /// Host memory total (MB), constant for the duration of a run.
+    pub system_memory_total_mb: f64,
+    pub system_free_samples: Vec<f64>,
+    pub system_used_samples: Vec<f64>,
+    pub swap_used_samples: Vec<f64>,
 }
 
 /// Internal sampling data protected by a mutex.
@@ -32,6 +91,17 @@ struct SamplingData {
     cpu_tauri_samples: Vec<f64>,
     cpu_system_samples: Vec<f64>,
     memory_samples: Vec<f64>,
+    resource_timeseries: Vec<ResourceSamplePoint>,
+    gpu_utilization_samples: Vec<f64>,
+    gpu_vram_samples: Vec<f64>,
+    gpu_power_samples: Vec<f64>,
+    gpu_temperature_samples: Vec<f64>,
+    ane_power_samples: Vec<f64>,
+    ane_residency_samples: Vec<f64>,
+    system_memory_total_mb: f64,
+    system_free_samples: Vec<f64>,
+    system_used_samples: Vec<f64>,
+    swap_used_samples: Vec<f64>,
     peak_memory: f64,
     sampling_active: bool,
 }
@@ -55,6 +125,17 @@ impl SamplingState {
                 cpu_tauri_samples: Vec::with_capacity(100),
                 cpu_system_samples: Vec::with_capacity(100),
                 memory_samples: Vec::with_capacity(100),
+                resource_timeseries: Vec::with_capacity(100),
+                gpu_utilization_samples: Vec::new(),
+                gpu_vram_samples: Vec::new(),
+                gpu_power_samples: Vec::new(),
+                gpu_temperature_samples: Vec::new(),
+                ane_power_samples: Vec::new(),
+                ane_residency_samples: Vec::new(),
+                system_memory_total_mb: 0.0,
+                system_free_samples: Vec::with_capacity(100),
+                system_used_samples: Vec::with_capacity(100),
+                swap_used_samples: Vec::with_capacity(100),
                 peak_memory: initial_memory,
                 sampling_active: true,
             })),
@@ -62,12 +143,53 @@ impl SamplingState {
     }
 
     /// Record a sample (single lock acquisition for all metrics).
-    pub fn record_sample(&self, ollama_cpu: f64, tauri_cpu: f64, system_cpu: f64, memory: f64) {
+    ///
+    /// `gpu` fields that are `None` (no telemetry backend for this tick) are
+    /// simply omitted from their sample vectors rather than recorded as 0.0,
+    /// so averages aren't skewed downward on GPU-telemetry-less machines.
+    pub fn record_sample(
+        &self,
+        ollama_cpu: f64,
+        tauri_cpu: f64,
+        system_cpu: f64,
+        memory: f64,
+        elapsed_ms: f64,
+        gpu: super::gpu::GpuSample,
+        system_memory: SystemMemorySample,
+    ) {
         let mut data = self.inner.lock().expect("SamplingState mutex poisoned");
         data.cpu_ollama_samples.push(ollama_cpu);
         data.cpu_tauri_samples.push(tauri_cpu);
         data.cpu_system_samples.push(system_cpu);
         data.memory_samples.push(memory);
+        data.resource_timeseries.push(ResourceSamplePoint {
+            elapsed_ms,
+            cpu_ollama_percent: ollama_cpu,
+            cpu_tauri_percent: tauri_cpu,
+            memory_mb: memory,
+        });
+        if let Some(v) = gpu.utilization_percent {
+            data.gpu_utilization_samples.push(v);
+        }
+        if let Some(v) = gpu.vram_used_mb {
+            data.gpu_vram_samples.push(v);
+        }
+        if let Some(v) = gpu.power_watts {
+            data.gpu_power_samples.push(v);
+        }
+        if let Some(v) = gpu.temperature_c {
+            data.gpu_temperature_samples.push(v);
+        }
+        if let Some(v) = gpu.ane_power_watts {
+            data.ane_power_samples.push(v);
+        }
+        if let Some(v) = gpu.ane_residency_percent {
+            data.ane_residency_samples.push(v);
+        }
+        data.system_memory_total_mb = system_memory.total_mb;
+        data.system_free_samples.push(system_memory.free_mb);
+        data.system_used_samples.push(system_memory.used_mb);
+        data.swap_used_samples.push(system_memory.swap_used_mb);
         if memory > data.peak_memory {
             data.peak_memory = memory;
         }
@@ -96,6 +218,17 @@ impl SamplingState {
             cpu_tauri_samples: std::mem::take(&mut data.cpu_tauri_samples),
             cpu_system_samples: std::mem::take(&mut data.cpu_system_samples),
             memory_samples: std::mem::take(&mut data.memory_samples),
+            resource_timeseries: std::mem::take(&mut data.resource_timeseries),
+            gpu_utilization_samples: std::mem::take(&mut data.gpu_utilization_samples),
+            gpu_vram_samples: std::mem::take(&mut data.gpu_vram_samples),
+            gpu_power_samples: std::mem::take(&mut data.gpu_power_samples),
+            gpu_temperature_samples: std::mem::take(&mut data.gpu_temperature_samples),
+            ane_power_samples: std::mem::take(&mut data.ane_power_samples),
+            ane_residency_samples: std::mem::take(&mut data.ane_residency_samples),
+            system_memory_total_mb: data.system_memory_total_mb,
+            system_free_samples: std::mem::take(&mut data.system_free_samples),
+            system_used_samples: std::mem::take(&mut data.system_used_samples),
+            swap_used_samples: std::mem::take(&mut data.swap_used_samples),
             peak_memory_mb: data.peak_memory,
         })
     }
@@ -110,6 +243,7 @@ impl SamplingState {
 pub fn spawn_resource_sampler(
     ollama_pid: sysinfo::Pid,
     state: SamplingState,
+    gpu_vendor: GpuVendor,
 ) -> tokio::sync::oneshot::Receiver<()> {
     let (tx, rx) = tokio::sync::oneshot::channel();
     let tauri_pid = sysinfo::Pid::from_u32(std::process::id());
@@ -123,6 +257,15 @@ pub fn spawn_resource_sampler(
         tokio::time::sleep(CPU_BASELINE_DELAY).await;
         sys.refresh_cpu_all();
 
+        // Built once per run rather than per-tick: NVML/rocm-smi probing has
+        // real setup cost, and the vendor can't change mid-run.
+        let gpu_sampler = GpuSampler::new(&gpu_vendor);
+
+        // Reference point for `resource_timeseries`'s `elapsed_ms`, taken
+        // after the CPU baseline delay so the curve starts at the first
+        // real sample rather than including the baseline wait.
+        let sampling_start = Instant::now();
+
         while state.is_active() {
             sys.refresh_all();
             sys.refresh_cpu_all();
@@ -145,7 +288,10 @@ pub fn spawn_resource_sampler(
             };
 
             if let Some((ollama_cpu, memory)) = ollama_data {
-                state.record_sample(ollama_cpu, tauri_cpu, system_cpu, memory);
+                let gpu_sample = gpu_sampler.sample(ollama_pid);
+                let system_memory = SystemMemorySample::from_system(&sys);
+                let elapsed_ms = sampling_start.elapsed().as_secs_f64() * 1000.0;
+                state.record_sample(ollama_cpu, tauri_cpu, system_cpu, memory, elapsed_ms, gpu_sample, system_memory);
             } else {
                 log::warn!("Ollama process (PID {ollama_pid}) disappeared during sampling");
                 break;
@@ -197,6 +343,20 @@ pub fn calculate_median(values: &[f64]) -> f64 {
     }
 }
 
+/// Percentile (0-100) of values, nearest-rank after sorting (same
+/// `total_cmp`-based sort as `calculate_median` for NaN safety).
+pub fn calculate_percentile(values: &[f64], percentile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let rank = ((percentile / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
 /// Calculate average of values.
 pub fn calculate_average(values: &[f64]) -> f64 {
     if values.is_empty() {
@@ -204,3 +364,204 @@ pub fn calculate_average(values: &[f64]) -> f64 {
     }
     values.iter().sum::<f64>() / values.len() as f64
 }
+
+/// Average a sample vector, or `None` if it's empty (e.g. no GPU telemetry
+/// backend was available for this run) rather than reporting a misleading 0.0.
+pub fn average_if_present(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| calculate_average(values))
+}
+
+/// Minimum of values, or 0.0 if empty (consistent with `calculate_average`/`calculate_median`).
+pub fn calculate_min(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().copied().fold(f64::INFINITY, f64::min)
+}
+
+/// Maximum of values, or 0.0 if empty (consistent with `calculate_average`/`calculate_median`).
+pub fn calculate_max(values: &[f64]) -> f64 {
+    values.iter().copied().fold(0.0, f64::max)
+}
+
+/// Richer percentile/spread summary of a post-run sample set than
+/// `calculate_median`/`calculate_average` alone expose - p50/p90/p95/p99 via
+/// linear interpolation between ranks, sample standard deviation, min/max,
+/// and coefficient of variation, for spotting a long tail (e.g. an Ollama
+/// first-load stall) that a mean alone would wash out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleSummary {
+    pub count: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Sample standard deviation (`N-1` denominator).
+    pub std_dev: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+    /// `std_dev / mean`, or 0.0 if `mean` is 0 - a dimensionless spread
+    /// useful for comparing tails across metrics on different scales (e.g.
+    /// CPU percent vs memory MB).
+    pub coefficient_of_variation: f64,
+}
+
+/// Summarize `samples` into a `SampleSummary`. All fields are 0.0 for an
+/// empty slice; `std_dev`/`coefficient_of_variation` are 0.0 for a
+/// single-element slice (not enough data to estimate spread), with every
+/// percentile equal to that one value.
+pub fn summarize(samples: &[f64]) -> SampleSummary {
+    if samples.is_empty() {
+        return SampleSummary {
+            count: 0,
+            mean: 0.0,
+            min: 0.0,
+            max: 0.0,
+            std_dev: 0.0,
+            p50: 0.0,
+            p90: 0.0,
+            p95: 0.0,
+            p99: 0.0,
+            coefficient_of_variation: 0.0,
+        };
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let count = sorted.len();
+    let mean = calculate_average(&sorted);
+    let min = sorted[0];
+    let max = sorted[count - 1];
+
+    let std_dev = if count < 2 {
+        0.0
+    } else {
+        let variance =
+            sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64;
+        variance.sqrt()
+    };
+    let coefficient_of_variation = if mean == 0.0 { 0.0 } else { std_dev / mean };
+
+    SampleSummary {
+        count,
+        mean,
+        min,
+        max,
+        std_dev,
+        p50: interpolated_percentile(&sorted, 50.0),
+        p90: interpolated_percentile(&sorted, 90.0),
+        p95: interpolated_percentile(&sorted, 95.0),
+        p99: interpolated_percentile(&sorted, 99.0),
+        coefficient_of_variation,
+    }
+}
+
+/// Percentile (0-100) of an already-`total_cmp`-sorted slice, via linear
+/// interpolation between the two bracketing ranks - distinct from
+/// `calculate_percentile`'s rounded-nearest-rank convention, matching how
+/// percentiles are conventionally reported for resource/latency tails.
+/// Handles a single-element slice by returning that element for any
+/// percentile (empty slices are handled by `summarize` before this is called).
+fn interpolated_percentile(sorted: &[f64], percentile: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let rank = (percentile / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    if lower == upper {
+        return sorted[lower];
+    }
+
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/// Discard the samples recorded during the first `discard_ms` of a run (the
+/// CPU ramp after `CPU_BASELINE_DELAY`), so a `summarize` over the remainder
+/// reflects steady-state inference rather than the startup spike. Returns
+/// the subset of `series` with `elapsed_ms >= discard_ms`; callers then map
+/// out the field they want (e.g. `cpu_ollama_percent`) and pass that to
+/// `summarize`.
+pub fn trim_warmup(series: &[ResourceSamplePoint], discard_ms: f64) -> Vec<ResourceSamplePoint> {
+    series
+        .iter()
+        .copied()
+        .filter(|point| point.elapsed_ms >= discard_ms)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_empty_samples() {
+        let summary = summarize(&[]);
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.mean, 0.0);
+        assert_eq!(summary.std_dev, 0.0);
+        assert_eq!(summary.coefficient_of_variation, 0.0);
+    }
+
+    #[test]
+    fn test_summarize_single_sample_is_degenerate() {
+        let summary = summarize(&[42.0]);
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.mean, 42.0);
+        assert_eq!(summary.min, 42.0);
+        assert_eq!(summary.max, 42.0);
+        assert_eq!(summary.std_dev, 0.0);
+        assert_eq!(summary.p50, 42.0);
+        assert_eq!(summary.p99, 42.0);
+    }
+
+    #[test]
+    fn test_summarize_percentiles_interpolate_between_ranks() {
+        // 0..=100 in steps of 10 (11 values); p50 and p90 land exactly on a
+        // rank, p95 (rank 9.5) does not and should interpolate to 95 rather
+        // than round to 100.
+        let samples: Vec<f64> = (0..=10).map(|i| i as f64 * 10.0).collect();
+        let summary = summarize(&samples);
+
+        assert_eq!(summary.min, 0.0);
+        assert_eq!(summary.max, 100.0);
+        assert_eq!(summary.p50, 50.0);
+        assert_eq!(summary.p90, 90.0);
+        assert_eq!(summary.p95, 95.0);
+    }
+
+    #[test]
+    fn test_summarize_coefficient_of_variation() {
+        let summary = summarize(&[10.0, 10.0, 10.0, 10.0]);
+        assert_eq!(summary.std_dev, 0.0);
+        assert_eq!(summary.coefficient_of_variation, 0.0);
+
+        let summary = summarize(&[8.0, 9.0, 11.0, 12.0]);
+        assert!(summary.std_dev > 0.0);
+        assert!((summary.coefficient_of_variation - summary.std_dev / summary.mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trim_warmup_discards_samples_before_cutoff() {
+        let series = vec![
+            ResourceSamplePoint { elapsed_ms: 0.0, cpu_ollama_percent: 1.0, cpu_tauri_percent: 0.0, memory_mb: 0.0 },
+            ResourceSamplePoint { elapsed_ms: 50.0, cpu_ollama_percent: 2.0, cpu_tauri_percent: 0.0, memory_mb: 0.0 },
+            ResourceSamplePoint { elapsed_ms: 150.0, cpu_ollama_percent: 3.0, cpu_tauri_percent: 0.0, memory_mb: 0.0 },
+            ResourceSamplePoint { elapsed_ms: 300.0, cpu_ollama_percent: 4.0, cpu_tauri_percent: 0.0, memory_mb: 0.0 },
+        ];
+
+        let trimmed = trim_warmup(&series, 150.0);
+        assert_eq!(trimmed.len(), 2);
+        assert_eq!(trimmed[0].cpu_ollama_percent, 3.0);
+        assert_eq!(trimmed[1].cpu_ollama_percent, 4.0);
+    }
+
+    #[test]
+    fn test_trim_warmup_empty_series() {
+        assert!(trim_warmup(&[], 100.0).is_empty());
+    }
+}
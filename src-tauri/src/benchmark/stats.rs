@@ -0,0 +1,304 @@
+//! Statistical summarization of metric samples: bootstrap confidence
+//! intervals and Tukey-fence outlier tagging.
+//!
+//! Used by [`super::export`] to turn noisy per-iteration numbers into a
+//! `(category, model)` summary that can tell a real change apart from
+//! measurement noise.
+
+use rand::Rng;
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+/// Bootstrap resamples drawn per confidence interval. ~1000 is the
+/// conventional floor for a stable 95% CI from a percentile bootstrap.
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Coefficient on `N^(1/3)` for the Newey-West bandwidth (how many lags of
+/// autocovariance to include): grows sub-linearly with sample count, per the
+/// conventional Newey-West rule of thumb.
+const BANDWIDTH_COEFFICIENT: f64 = 0.5;
+
+/// Below this sample count, a bootstrap CI and Tukey fences are too noisy to
+/// be meaningful - skip them and report only the raw mean.
+const MIN_SAMPLES_FOR_STATS: usize = 4;
+
+/// Tukey-fence multiplier for a "mild" outlier.
+const MILD_OUTLIER_IQR_MULTIPLIER: f64 = 1.5;
+/// Tukey-fence multiplier for a "severe" outlier.
+const SEVERE_OUTLIER_IQR_MULTIPLIER: f64 = 3.0;
+
+/// Statistical summary of one metric's samples within a `(category, model)`
+/// group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricStats {
+    pub sample_count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    /// 95% bootstrap confidence interval on the mean. `None` if
+    /// `sample_count < MIN_SAMPLES_FOR_STATS`.
+    pub ci_95: Option<(f64, f64)>,
+    /// Count of samples beyond the mild Tukey fence (`1.5*IQR`) but within
+    /// the severe one. `None` alongside `ci_95`.
+    pub mild_outliers: Option<usize>,
+    /// Count of samples beyond the severe Tukey fence (`3*IQR`). `None`
+    /// alongside `ci_95`.
+    pub severe_outliers: Option<usize>,
+}
+
+/// Summarize `samples` (e.g. every `tokens_per_second` value in a
+/// `(category, model)` group): mean/median/std-dev always, plus a bootstrap
+/// CI and Tukey-fence outlier counts when there are enough samples to
+/// support them.
+pub fn summarize(samples: &[f64]) -> MetricStats {
+    let sample_count = samples.len();
+    let mean = super::sampling::calculate_average(samples);
+    let median = super::sampling::calculate_median(samples);
+    let std_dev = standard_deviation(samples, mean);
+
+    if sample_count < MIN_SAMPLES_FOR_STATS {
+        return MetricStats {
+            sample_count,
+            mean,
+            median,
+            std_dev,
+            ci_95: None,
+            mild_outliers: None,
+            severe_outliers: None,
+        };
+    }
+
+    let (mild_outliers, severe_outliers) = count_tukey_outliers(samples);
+
+    MetricStats {
+        sample_count,
+        mean,
+        median,
+        std_dev,
+        ci_95: Some(bootstrap_ci_95(samples)),
+        mild_outliers: Some(mild_outliers),
+        severe_outliers: Some(severe_outliers),
+    }
+}
+
+fn standard_deviation(samples: &[f64], mean: f64) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let variance =
+        samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    variance.sqrt()
+}
+
+/// 95% confidence interval on the mean via percentile bootstrap: resample
+/// `samples` with replacement `BOOTSTRAP_RESAMPLES` times, take each
+/// resample's mean, then report the 2.5th/97.5th percentiles of those means.
+fn bootstrap_ci_95(samples: &[f64]) -> (f64, f64) {
+    let mut rng = rand::thread_rng();
+    let mut resample_means = Vec::with_capacity(BOOTSTRAP_RESAMPLES);
+
+    for _ in 0..BOOTSTRAP_RESAMPLES {
+        let resample_mean = (0..samples.len())
+            .map(|_| samples[rng.gen_range(0..samples.len())])
+            .sum::<f64>()
+            / samples.len() as f64;
+        resample_means.push(resample_mean);
+    }
+
+    let lower = super::sampling::calculate_percentile(&resample_means, 2.5);
+    let upper = super::sampling::calculate_percentile(&resample_means, 97.5);
+    (lower, upper)
+}
+
+/// Tukey-fence outlier counts: mild beyond `1.5*IQR`, severe beyond
+/// `3*IQR` (a severe outlier is not also counted as mild). Zero-variance
+/// samples (`IQR == 0`) report no outliers rather than flagging every point
+/// that isn't exactly the median.
+fn count_tukey_outliers(samples: &[f64]) -> (usize, usize) {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let q1 = super::sampling::calculate_percentile(&sorted, 25.0);
+    let q3 = super::sampling::calculate_percentile(&sorted, 75.0);
+    let iqr = q3 - q1;
+
+    if iqr <= 0.0 {
+        return (0, 0);
+    }
+
+    let mild_lower = q1 - MILD_OUTLIER_IQR_MULTIPLIER * iqr;
+    let mild_upper = q3 + MILD_OUTLIER_IQR_MULTIPLIER * iqr;
+    let severe_lower = q1 - SEVERE_OUTLIER_IQR_MULTIPLIER * iqr;
+    let severe_upper = q3 + SEVERE_OUTLIER_IQR_MULTIPLIER * iqr;
+
+    let mut mild = 0;
+    let mut severe = 0;
+    for &v in samples {
+        if v < severe_lower || v > severe_upper {
+            severe += 1;
+        } else if v < mild_lower || v > mild_upper {
+            mild += 1;
+        }
+    }
+
+    (mild, severe)
+}
+
+/// Newey-West bandwidth: number of autocovariance lags to include, growing
+/// sub-linearly with `n` so it stays small relative to the sample count.
+fn bartlett_bandwidth(n: usize) -> usize {
+    let k = (BANDWIDTH_COEFFICIENT * (n as f64).powf(1.0 / 3.0)).round() as usize;
+    k.clamp(1, n.saturating_sub(1).max(1))
+}
+
+/// Sample autocovariance at `lag`: `(1/N)*sum((x_i - mean)*(x_{i+lag} - mean))`.
+fn autocovariance(samples: &[f64], mean: f64, lag: usize) -> f64 {
+    let n = samples.len();
+    if lag >= n {
+        return 0.0;
+    }
+    let sum: f64 = (0..n - lag).map(|i| (samples[i] - mean) * (samples[i + lag] - mean)).sum();
+    sum / n as f64
+}
+
+/// Newey-West long-run variance: `gamma_0 + 2*sum(w_k * gamma_k)` for
+/// lags `k=1..K`, with Bartlett taper weights `w_k = 1 - k/(K+1)`. Corrects
+/// the naive sample variance for serial correlation between consecutive
+/// samples (e.g. back-to-back benchmark iterations warming the same cache).
+fn long_run_variance(samples: &[f64], mean: f64) -> f64 {
+    let bandwidth = bartlett_bandwidth(samples.len());
+    let gamma_0 = autocovariance(samples, mean, 0);
+
+    let weighted_sum: f64 = (1..=bandwidth)
+        .map(|lag| {
+            let weight = 1.0 - (lag as f64) / (bandwidth as f64 + 1.0);
+            weight * autocovariance(samples, mean, lag)
+        })
+        .sum();
+
+    (gamma_0 + 2.0 * weighted_sum).max(0.0)
+}
+
+/// Confidence interval on `samples`'s mean that accounts for serial
+/// correlation between consecutive samples, via a Newey-West long-run
+/// variance estimate and a Student's-T quantile for `N-1` degrees of
+/// freedom. A naive `std/sqrt(N)` standard error understates uncertainty
+/// when back-to-back benchmark iterations are correlated (shared cache
+/// state, thermal drift), making two models look "significantly different"
+/// when the gap is within measurement noise.
+///
+/// `confidence` is the interval width as a fraction (e.g. `0.95` for a 95%
+/// CI). Degenerate for fewer than 2 samples: returns `(mean, mean)`, since
+/// there isn't enough data to estimate any spread.
+pub fn autocorrelation_corrected_ci(samples: &[f64], confidence: f64) -> (f64, f64) {
+    let n = samples.len();
+    let mean = super::sampling::calculate_average(samples);
+
+    if n < 2 {
+        return (mean, mean);
+    }
+
+    let sigma_sq = long_run_variance(samples, mean);
+    let standard_error = (sigma_sq / n as f64).sqrt();
+
+    let degrees_of_freedom = (n - 1) as f64;
+    let t_dist = StudentsT::new(0.0, 1.0, degrees_of_freedom)
+        .expect("N-1 degrees of freedom is always positive here (n >= 2)");
+    let alpha = 1.0 - confidence;
+    let t_value = t_dist.inverse_cdf(1.0 - alpha / 2.0);
+
+    (mean - t_value * standard_error, mean + t_value * standard_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_below_min_samples_skips_ci_and_outliers() {
+        let stats = summarize(&[1.0, 2.0, 3.0]);
+
+        assert_eq!(stats.sample_count, 3);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.median, 2.0);
+        assert_eq!(stats.ci_95, None);
+        assert_eq!(stats.mild_outliers, None);
+        assert_eq!(stats.severe_outliers, None);
+    }
+
+    #[test]
+    fn test_summarize_zero_variance_has_no_outliers() {
+        let stats = summarize(&[5.0, 5.0, 5.0, 5.0, 5.0]);
+
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.mild_outliers, Some(0));
+        assert_eq!(stats.severe_outliers, Some(0));
+    }
+
+    #[test]
+    fn test_summarize_ci_brackets_the_mean() {
+        let samples = [10.0, 11.0, 9.0, 10.5, 9.5, 10.2, 9.8, 10.1];
+        let stats = summarize(&samples);
+
+        let (lower, upper) = stats.ci_95.expect("should compute a CI with 8 samples");
+        assert!(lower <= stats.mean, "CI lower bound should be at or below the mean");
+        assert!(upper >= stats.mean, "CI upper bound should be at or above the mean");
+    }
+
+    #[test]
+    fn test_count_tukey_outliers_flags_mild_and_severe() {
+        // Tight cluster around 10 (Q1=10, Q3=11, IQR=1), one mild outlier
+        // (13, between the 12.5 and 14 fences), one severe (200).
+        let samples = [9.0, 10.0, 10.0, 11.0, 10.0, 9.5, 13.0, 200.0];
+        let (mild, severe) = count_tukey_outliers(&samples);
+
+        assert_eq!(severe, 1, "200.0 should be flagged severe");
+        assert_eq!(mild, 1, "13.0 should be flagged mild (not severe)");
+    }
+
+    #[test]
+    fn test_summarize_empty_samples() {
+        let stats = summarize(&[]);
+
+        assert_eq!(stats.sample_count, 0);
+        assert_eq!(stats.mean, 0.0);
+        assert_eq!(stats.ci_95, None);
+    }
+
+    #[test]
+    fn test_autocorrelation_corrected_ci_brackets_the_mean() {
+        let samples = [10.0, 11.0, 9.0, 10.5, 9.5, 10.2, 9.8, 10.1];
+        let mean = super::super::sampling::calculate_average(&samples);
+
+        let (lower, upper) = autocorrelation_corrected_ci(&samples, 0.95);
+
+        assert!(lower <= mean, "CI lower bound should be at or below the mean");
+        assert!(upper >= mean, "CI upper bound should be at or above the mean");
+    }
+
+    #[test]
+    fn test_autocorrelation_corrected_ci_widens_for_correlated_series() {
+        // Both series have mean 10, and the alternating one has the larger
+        // naive variance, but its lag autocorrelations alternate in sign
+        // (strongly negative at lag 1) while the trending one's are strongly
+        // positive, so the long-run variance - and the interval - is wider
+        // for the trending series.
+        let alternating: Vec<f64> = (0..40).map(|i| if i % 2 == 0 { 9.0 } else { 11.0 }).collect();
+        let trending: Vec<f64> = (0..40).map(|i| 9.0 + (i as f64 / 39.0) * 2.0).collect();
+
+        let (alt_lower, alt_upper) = autocorrelation_corrected_ci(&alternating, 0.95);
+        let (trend_lower, trend_upper) = autocorrelation_corrected_ci(&trending, 0.95);
+
+        assert!(
+            (trend_upper - trend_lower) > (alt_upper - alt_lower),
+            "a trending (positively autocorrelated) series should get a wider CI than an alternating one"
+        );
+    }
+
+    #[test]
+    fn test_autocorrelation_corrected_ci_degenerate_single_sample() {
+        let (lower, upper) = autocorrelation_corrected_ci(&[5.0], 0.95);
+        assert_eq!(lower, 5.0);
+        assert_eq!(upper, 5.0);
+    }
+}
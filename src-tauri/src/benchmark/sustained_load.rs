@@ -0,0 +1,217 @@
+//! Sustained-rate load mode: fire requests at a target operations-per-second
+//! for a fixed wall-clock duration and report achieved throughput plus
+//! p50/p90/p99 latency, the way windsock-style load tests do.
+//!
+//! Unlike the sequential `run_single_test` iterations the rest of the suite
+//! runs (one prompt at a time, with full resource sampling around each),
+//! this fires requests on a fixed schedule without waiting for prior
+//! requests to finish, so a backend that can't keep up shows up as queueing
+//! - rising tail latency - rather than the benchmark self-throttling to
+//! match completion time.
+
+use super::test_suite::get_test_suite;
+use crate::commands::ollama::{OllamaConfig, OllamaMessage, OllamaRequest, OllamaResponse};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Parameters for a sustained-rate load run.
+#[derive(Debug, Clone)]
+pub struct SustainedLoadConfig {
+    pub model: String,
+    pub target_ops_per_sec: f64,
+    pub duration_secs: u64,
+}
+
+/// One request's latency observations within a sustained-load run.
+struct RequestLatency {
+    first_token_ms: f64,
+    total_time_ms: f64,
+}
+
+/// Throughput and tail-latency result of a sustained-rate load run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SustainedLoadResult {
+    pub model: String,
+    pub target_ops_per_sec: f64,
+    pub duration_secs: u64,
+    pub requests_completed: usize,
+    pub requests_failed: usize,
+    pub achieved_ops_per_sec: f64,
+    pub p50_first_token_ms: f64,
+    pub p90_first_token_ms: f64,
+    pub p99_first_token_ms: f64,
+    pub p50_total_time_ms: f64,
+    pub p90_total_time_ms: f64,
+    pub p99_total_time_ms: f64,
+}
+
+/// Nearest-rank percentile: sort, then index at `ceil(p/100 * n) - 1`,
+/// clamped to the valid range. Distinct from `sampling::calculate_percentile`,
+/// which rounds `p/100 * (n-1)` to the closest index - load-test tail latency
+/// is conventionally reported via this ceiling-based nearest-rank.
+fn nearest_rank_percentile(values: &[f64], percentile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let n = sorted.len();
+    let rank = ((percentile / 100.0) * n as f64).ceil() as isize - 1;
+    let idx = rank.clamp(0, n as isize - 1) as usize;
+    sorted[idx]
+}
+
+/// Issue one non-streaming chat request and report its first-token/total
+/// latency.
+async fn issue_request(
+    client: &reqwest::Client,
+    config: &OllamaConfig,
+    model: &str,
+    prompt: &str,
+) -> Result<RequestLatency, String> {
+    let request = OllamaRequest {
+        model: model.to_string(),
+        messages: vec![OllamaMessage { role: "user".to_string(), content: prompt.to_string() }],
+        stream: false,
+    };
+
+    let url = format!("{}/api/chat", config.base_url()?);
+    let start = Instant::now();
+
+    let response = client
+        .post(&url)
+        .json(&request)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Sustained-load request failed: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Ollama returned error status {}", response.status()));
+    }
+
+    let ollama_response: OllamaResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse sustained-load response: {e}"))?;
+
+    let total_time_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let first_token_ms = ollama_response
+        .prompt_eval_duration
+        .map_or(0.0, |ns| (ns as f64) / 1_000_000.0);
+
+    Ok(RequestLatency { first_token_ms, total_time_ms })
+}
+
+/// Run a sustained-rate load test: fire requests at
+/// `load_config.target_ops_per_sec` for `load_config.duration_secs`,
+/// cycling through the built-in prompt suite, and report achieved
+/// throughput plus p50/p90/p99 latency for first-token and total-response
+/// time.
+pub async fn run_sustained_load(
+    client: &reqwest::Client,
+    config: &OllamaConfig,
+    load_config: &SustainedLoadConfig,
+) -> Result<SustainedLoadResult, String> {
+    let prompts: Vec<String> = get_test_suite().into_iter().map(|p| p.prompt).collect();
+    if prompts.is_empty() {
+        return Err("No prompts available for sustained load".to_string());
+    }
+
+    let interval = Duration::from_secs_f64(1.0 / load_config.target_ops_per_sec.max(0.001));
+    let bench_duration = Duration::from_secs(load_config.duration_secs);
+
+    let mut handles = Vec::new();
+    let run_start = Instant::now();
+    let mut ticker = tokio::time::interval(interval);
+    let mut prompt_idx = 0usize;
+
+    while run_start.elapsed() < bench_duration {
+        ticker.tick().await;
+
+        let prompt = prompts[prompt_idx % prompts.len()].clone();
+        prompt_idx += 1;
+
+        let client = client.clone();
+        let config = config.clone();
+        let model = load_config.model.clone();
+        handles.push(tokio::spawn(async move {
+            issue_request(&client, &config, &model, &prompt).await
+        }));
+    }
+
+    let mut first_token_samples = Vec::new();
+    let mut total_time_samples = Vec::new();
+    let mut requests_failed = 0usize;
+
+    for handle in handles {
+        match handle.await {
+            Ok(Ok(latency)) => {
+                first_token_samples.push(latency.first_token_ms);
+                total_time_samples.push(latency.total_time_ms);
+            }
+            Ok(Err(e)) => {
+                log::warn!("Sustained-load request failed: {e}");
+                requests_failed += 1;
+            }
+            Err(e) => {
+                log::warn!("Sustained-load request task panicked: {e}");
+                requests_failed += 1;
+            }
+        }
+    }
+
+    let requests_completed = first_token_samples.len();
+    let elapsed_secs = run_start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+    Ok(SustainedLoadResult {
+        model: load_config.model.clone(),
+        target_ops_per_sec: load_config.target_ops_per_sec,
+        duration_secs: load_config.duration_secs,
+        requests_completed,
+        requests_failed,
+        achieved_ops_per_sec: requests_completed as f64 / elapsed_secs,
+        p50_first_token_ms: nearest_rank_percentile(&first_token_samples, 50.0),
+        p90_first_token_ms: nearest_rank_percentile(&first_token_samples, 90.0),
+        p99_first_token_ms: nearest_rank_percentile(&first_token_samples, 99.0),
+        p50_total_time_ms: nearest_rank_percentile(&total_time_samples, 50.0),
+        p90_total_time_ms: nearest_rank_percentile(&total_time_samples, 90.0),
+        p99_total_time_ms: nearest_rank_percentile(&total_time_samples, 99.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_rank_percentile_basic() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+
+        assert_eq!(nearest_rank_percentile(&values, 50.0), 5.0);
+        assert_eq!(nearest_rank_percentile(&values, 90.0), 9.0);
+        assert_eq!(nearest_rank_percentile(&values, 99.0), 10.0);
+    }
+
+    #[test]
+    fn test_nearest_rank_percentile_empty_is_zero() {
+        assert_eq!(nearest_rank_percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_nearest_rank_percentile_single_value() {
+        assert_eq!(nearest_rank_percentile(&[42.0], 99.0), 42.0);
+    }
+
+    #[test]
+    fn test_nearest_rank_percentile_clamps_within_bounds() {
+        let values = [10.0, 20.0, 30.0];
+        // p=100 would compute rank = n - 1 exactly (no clamping needed), but
+        // verify we never index out of bounds regardless.
+        assert_eq!(nearest_rank_percentile(&values, 100.0), 30.0);
+    }
+}
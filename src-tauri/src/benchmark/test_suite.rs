@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
 
 /// Categories of test prompts
+///
+/// `rename_all = "snake_case"` (with an explicit override on `FollowUp`) matches
+/// the lowercase/hyphenated convention `as_str()` uses below, so a hand-authored
+/// suite file can use the same `"short"`/`"follow-up"`/`"coding_session"` strings
+/// this codebase exports elsewhere instead of the PascalCase variant names.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PromptCategory {
     Short,
     Medium,
     Long,
+    #[serde(rename = "follow-up")]
     FollowUp,
+    /// A turn in the scripted multi-turn `CODING_SESSION_TURNS` workload.
+    CodingSession,
 }
 
 impl PromptCategory {
@@ -16,6 +27,7 @@ impl PromptCategory {
             PromptCategory::Medium => "medium",
             PromptCategory::Long => "long",
             PromptCategory::FollowUp => "follow-up",
+            PromptCategory::CodingSession => "coding_session",
         }
     }
 }
@@ -56,6 +68,20 @@ pub const FOLLOW_UP_PROMPTS: [&str; 3] = [
     "What are some common mistakes beginners make with this?",
 ];
 
+/// A scripted multi-turn "coding session": unlike the categories above, which
+/// are isolated single-shot prompts, each of these turns is sent with the
+/// full conversation so far, so context length grows turn over turn the way
+/// it does when a real user works a file over in a chat session (open a
+/// file, request a completion, ask for a follow-up edit, ask for an
+/// explanation, then navigate to another function).
+pub const CODING_SESSION_TURNS: [&str; 5] = [
+    "I have this Python file open:\n\n```python\ndef calculate_total(items):\n    pass\n```\n\nCan you complete calculate_total so it sums the price of each item?",
+    "Good start. Can you add a discount parameter that applies a percentage discount to the total?",
+    "Can you explain how the discount calculation works in the code you just wrote?",
+    "I've now navigated to a different function in the same file:\n\n```python\ndef format_receipt(items, total):\n    pass\n```\n\nCan you implement format_receipt to print each item and the total nicely?",
+    "Can you add comments to format_receipt explaining each step for a beginner?",
+];
+
 /// Generate the complete test suite
 pub fn get_test_suite() -> Vec<TestPrompt> {
     let mut suite = Vec::new();
@@ -99,8 +125,132 @@ pub fn get_test_suite() -> Vec<TestPrompt> {
     suite
 }
 
-/// Get total number of tests (prompts × iterations)
-pub fn get_total_test_count(iterations: usize) -> usize {
-    let prompts_per_iteration = SHORT_PROMPTS.len() + MEDIUM_PROMPTS.len() + LONG_PROMPTS.len() + FOLLOW_UP_PROMPTS.len();
-    prompts_per_iteration * iterations
+/// Get total number of tests (`suite.len()` × iterations), plus the
+/// fixed-size coding-session workload, which runs once per suite rather than
+/// once per iteration. Takes `suite` rather than recomputing the built-in
+/// counts so a loaded/merged suite (see `get_test_suite_from`) is reflected
+/// too.
+pub fn get_total_test_count(suite: &[TestPrompt], iterations: usize) -> usize {
+    suite.len() * iterations + CODING_SESSION_TURNS.len()
+}
+
+/// How a loaded custom prompt suite combines with the built-in one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuiteMergeMode {
+    /// Run only the prompts loaded from file.
+    Replace,
+    /// Run the built-in suite plus the loaded prompts.
+    Merge,
+}
+
+/// Load a custom prompt suite from a JSON or TOML file containing a list of
+/// `{id, category, prompt}` entries (see `TestPrompt`), and combine it with
+/// the built-in suite per `mode`.
+///
+/// File format is inferred from the extension: `.json` expects a top-level
+/// JSON array of entries; `.toml` expects a top-level `[[prompts]]` array of
+/// tables. Any other extension is an error. This lets users benchmark
+/// domain-specific prompts (their own codebase questions, non-English
+/// prompts, long-context stress tests) without recompiling.
+pub fn get_test_suite_from(path: &Path, mode: SuiteMergeMode) -> Result<Vec<TestPrompt>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read prompt suite file {path:?}: {e}"))?;
+
+    let loaded = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str::<Vec<TestPrompt>>(&contents)
+            .map_err(|e| format!("Failed to parse JSON prompt suite {path:?}: {e}"))?,
+        Some("toml") => {
+            #[derive(Deserialize)]
+            struct TomlSuite {
+                prompts: Vec<TestPrompt>,
+            }
+            toml::from_str::<TomlSuite>(&contents)
+                .map(|suite| suite.prompts)
+                .map_err(|e| format!("Failed to parse TOML prompt suite {path:?}: {e}"))?
+        }
+        other => {
+            return Err(format!(
+                "Unsupported prompt suite file extension {other:?} (expected .json or .toml)"
+            ))
+        }
+    };
+
+    match mode {
+        SuiteMergeMode::Replace => Ok(loaded),
+        SuiteMergeMode::Merge => {
+            let mut suite = get_test_suite();
+            suite.extend(loaded);
+            Ok(suite)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+    use std::io::Write;
+
+    #[test]
+    fn test_get_test_suite_from_json_replace() {
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"[{{"id": "custom_1", "category": "short", "prompt": "Hola, como estas?"}}]"#
+        )
+        .unwrap();
+
+        let suite = get_test_suite_from(file.path(), SuiteMergeMode::Replace)
+            .expect("JSON prompt suite should load");
+
+        assert_eq!(suite.len(), 1);
+        assert_eq!(suite[0].id, "custom_1");
+        assert_eq!(suite[0].prompt, "Hola, como estas?");
+    }
+
+    #[test]
+    fn test_get_test_suite_from_json_merge_includes_builtins() {
+        let mut file = NamedTempFile::with_suffix(".json").unwrap();
+        write!(
+            file,
+            r#"[{{"id": "custom_1", "category": "long", "prompt": "Custom long prompt"}}]"#
+        )
+        .unwrap();
+
+        let suite = get_test_suite_from(file.path(), SuiteMergeMode::Merge)
+            .expect("JSON prompt suite should load");
+
+        assert_eq!(suite.len(), get_test_suite().len() + 1);
+        assert!(suite.iter().any(|p| p.id == "custom_1"));
+    }
+
+    #[test]
+    fn test_get_test_suite_from_toml() {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        write!(
+            file,
+            "[[prompts]]\nid = \"custom_1\"\ncategory = \"medium\"\nprompt = \"Custom TOML prompt\"\n"
+        )
+        .unwrap();
+
+        let suite = get_test_suite_from(file.path(), SuiteMergeMode::Replace)
+            .expect("TOML prompt suite should load");
+
+        assert_eq!(suite.len(), 1);
+        assert_eq!(suite[0].id, "custom_1");
+    }
+
+    #[test]
+    fn test_get_test_suite_from_unsupported_extension_errors() {
+        let file = NamedTempFile::with_suffix(".yaml").unwrap();
+        let result = get_test_suite_from(file.path(), SuiteMergeMode::Replace);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_total_test_count_matches_suite_len() {
+        let suite = get_test_suite();
+        let count = get_total_test_count(&suite, 3);
+        assert_eq!(count, suite.len() * 3 + CODING_SESSION_TURNS.len());
+    }
 }
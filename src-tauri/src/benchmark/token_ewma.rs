@@ -0,0 +1,136 @@
+//! Exponentially-weighted moving average of inter-token latency, for a live
+//! "current speed" indicator while a response streams, as an alternative to
+//! `avg_token_latency_ms` which is only known once the whole response is in.
+//!
+//! Unlike a plain running average, the EWMA weights recent tokens more
+//! heavily (`new_ewma = alpha * sample + (1 - alpha) * prev_ewma`), so it
+//! tracks a slow-start-then-steady-state generation curve instead of
+//! smearing the slow first token(s) evenly across the whole estimate.
+
+use std::time::Instant;
+
+/// A single updated EWMA estimate, produced each time a token arrives.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EwmaSample {
+    /// Current EWMA of inter-token latency (ms).
+    pub ewma_ms: f64,
+    /// Instantaneous throughput implied by `ewma_ms` (tokens/sec).
+    pub tokens_per_sec: f64,
+}
+
+/// Streaming EWMA of inter-token latency. Feed it a timestamp per token via
+/// [`record_token`](Self::record_token) as tokens arrive.
+#[derive(Debug, Clone)]
+pub struct TokenLatencyEwma {
+    alpha: f64,
+    ewma_ms: Option<f64>,
+    last_token_at: Option<Instant>,
+}
+
+impl TokenLatencyEwma {
+    /// `alpha` is the smoothing factor in `(0, 1]`: closer to 1 tracks the
+    /// most recent token almost exclusively, closer to 0 smooths over more
+    /// history.
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            alpha,
+            ewma_ms: None,
+            last_token_at: None,
+        }
+    }
+
+    /// Record a token's arrival time and update the estimate.
+    ///
+    /// Returns `None` for the first token recorded, since there's no prior
+    /// timestamp yet to form a delta from.
+    pub fn record_token(&mut self, now: Instant) -> Option<EwmaSample> {
+        let sample = match self.last_token_at {
+            Some(prev) => {
+                let delta_ms = now.duration_since(prev).as_secs_f64() * 1000.0;
+                let ewma_ms = match self.ewma_ms {
+                    Some(prev_ewma) => self.alpha * delta_ms + (1.0 - self.alpha) * prev_ewma,
+                    None => delta_ms,
+                };
+                self.ewma_ms = Some(ewma_ms);
+                Some(EwmaSample {
+                    ewma_ms,
+                    tokens_per_sec: if ewma_ms > 0.0 { 1000.0 / ewma_ms } else { 0.0 },
+                })
+            }
+            None => None,
+        };
+
+        self.last_token_at = Some(now);
+        sample
+    }
+
+    /// The most recent estimate, or `None` if fewer than two tokens have
+    /// been recorded.
+    pub fn current(&self) -> Option<EwmaSample> {
+        self.ewma_ms.map(|ewma_ms| EwmaSample {
+            ewma_ms,
+            tokens_per_sec: if ewma_ms > 0.0 { 1000.0 / ewma_ms } else { 0.0 },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_first_token_produces_no_sample() {
+        let mut ewma = TokenLatencyEwma::new(0.5);
+        assert_eq!(ewma.record_token(Instant::now()), None);
+    }
+
+    #[test]
+    fn test_second_token_seeds_ewma_with_raw_delta() {
+        let mut ewma = TokenLatencyEwma::new(0.5);
+        let t0 = Instant::now();
+        ewma.record_token(t0);
+        let sample = ewma.record_token(t0 + Duration::from_millis(100)).unwrap();
+
+        assert!((sample.ewma_ms - 100.0).abs() < 1e-6);
+        assert!((sample.tokens_per_sec - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ewma_tracks_toward_steady_state() {
+        let mut ewma = TokenLatencyEwma::new(0.5);
+        let t0 = Instant::now();
+        ewma.record_token(t0);
+        // A slow first gap (model still warming up)...
+        ewma.record_token(t0 + Duration::from_millis(200));
+        // ...followed by consistently fast steady-state gaps.
+        let mut t = t0 + Duration::from_millis(200);
+        let mut last = None;
+        for _ in 0..10 {
+            t += Duration::from_millis(20);
+            last = ewma.record_token(t);
+        }
+
+        let sample = last.unwrap();
+        assert!(sample.ewma_ms < 200.0);
+        assert!((sample.ewma_ms - 20.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_current_matches_last_sample() {
+        let mut ewma = TokenLatencyEwma::new(0.3);
+        let t0 = Instant::now();
+        ewma.record_token(t0);
+        let sample = ewma.record_token(t0 + Duration::from_millis(50)).unwrap();
+
+        assert_eq!(ewma.current(), Some(sample));
+    }
+
+    #[test]
+    fn test_current_is_none_before_two_tokens() {
+        let mut ewma = TokenLatencyEwma::new(0.5);
+        assert_eq!(ewma.current(), None);
+        ewma.record_token(Instant::now());
+        assert_eq!(ewma.current(), None);
+    }
+}
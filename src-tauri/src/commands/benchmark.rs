@@ -1,6 +1,7 @@
 use super::errors::Error;
 use crate::benchmark::{
-    create_readme, export_to_csv, run_benchmark_suite, BenchmarkResults,
+    create_readme, export_summary_csv, export_to_csv, run_benchmark_suite, BenchmarkResults,
+    SustainedLoadConfig,
 };
 use crate::commands::ollama::{HttpClient, OllamaConfig};
 use tauri::{AppHandle, Emitter, State};
@@ -11,20 +12,49 @@ pub async fn run_benchmark(
     app_handle: AppHandle,
     model: String,
     iterations: Option<usize>,
+    warmup_iterations: Option<usize>,
+    stabilize_cpu: Option<bool>,
+    sustained_load_ops_per_sec: Option<f64>,
+    sustained_load_duration_secs: Option<u64>,
     client: State<'_, HttpClient>,
     config: State<'_, OllamaConfig>,
 ) -> Result<BenchmarkResults, Error> {
     let iterations = iterations.unwrap_or(3); // Default to 3 iterations
+    let warmup_iterations = warmup_iterations.unwrap_or(1); // Skip first iteration by default (cold cache/model load)
+    let stabilize_cpu = stabilize_cpu.unwrap_or(false); // Opt-in: requires cpufreq sysfs write access
+
+    // Opt-in: only run the sustained-load phase when both the target rate
+    // and duration are given.
+    let sustained_load_config = sustained_load_ops_per_sec.zip(sustained_load_duration_secs).map(
+        |(target_ops_per_sec, duration_secs)| SustainedLoadConfig {
+            model: model.clone(),
+            target_ops_per_sec,
+            duration_secs,
+        },
+    );
 
     // Create README if it doesn't exist
     create_readme()
         .map_err(|e| Error::Other(format!("Failed to create benchmark README file: {}", e)))?;
 
     // Run benchmark with progress updates
-    let results = run_benchmark_suite(model.clone(), iterations, client.get(), &config, |progress| {
-        // Emit progress event to frontend
-        let _ = app_handle.emit("benchmark_progress", progress);
-    })
+    let results = run_benchmark_suite(
+        model.clone(),
+        iterations,
+        warmup_iterations,
+        stabilize_cpu,
+        client.get(),
+        &config,
+        sustained_load_config,
+        |progress| {
+            // Emit progress event to frontend
+            let _ = app_handle.emit("benchmark_progress", progress);
+        },
+        |tokens_per_sec| {
+            // Emit a live "current speed" indicator while a response streams
+            let _ = app_handle.emit("benchmark_token_speed", tokens_per_sec);
+        },
+    )
     .await
     .map_err(|e| Error::Other(format!("Benchmark suite failed for model '{}': {}", model, e)))?;
 
@@ -32,6 +62,10 @@ pub async fn run_benchmark(
     let filepath = export_to_csv(&results, "benchmark")
         .map_err(|e| Error::Other(format!("Failed to export benchmark results to CSV: {}", e)))?;
 
+    // Overwrite the statistical summary alongside it
+    export_summary_csv(&results)
+        .map_err(|e| Error::Other(format!("Failed to export benchmark summary CSV: {}", e)))?;
+
     // Emit completion event with file path
     let _ = app_handle.emit(
         "benchmark_complete",
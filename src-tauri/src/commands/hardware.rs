@@ -1,5 +1,10 @@
-use crate::hardware::{self, types::HardwareInfo};
+use crate::hardware::{
+    self,
+    monitor::{HardwareMonitor, TelemetrySnapshot},
+    types::{GpuVendor, HardwareInfo},
+};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::OnceCell;
 
 /// State for caching hardware detection results
@@ -7,12 +12,17 @@ use tokio::sync::OnceCell;
 /// Stores Arc<HardwareInfo> internally for efficient sharing without cloning data
 pub struct HardwareCache {
     info: OnceCell<Arc<HardwareInfo>>,
+    /// Separate write-once slot for the scored variant, so the (slower)
+    /// capability micro-benchmark only ever runs once, and only for callers
+    /// that actually asked for it via `get_or_detect_with_score`.
+    scored_info: OnceCell<Arc<HardwareInfo>>,
 }
 
 impl Default for HardwareCache {
     fn default() -> Self {
         Self {
             info: OnceCell::new(),
+            scored_info: OnceCell::new(),
         }
     }
 }
@@ -39,6 +49,23 @@ impl HardwareCache {
     pub fn get(&self) -> Option<Arc<HardwareInfo>> {
         self.info.get().map(Arc::clone)
     }
+
+    /// Get cached hardware info including the synthetic capability score, or
+    /// detect (with the micro-benchmark) if not yet initialized. Kept behind
+    /// a separate `OnceCell` from `get_or_detect` so that plain hardware
+    /// detection stays cheap when the score isn't needed.
+    pub async fn get_or_detect_with_score(&self) -> Result<Arc<HardwareInfo>, String> {
+        self.scored_info
+            .get_or_try_init(|| async {
+                log::info!("Detecting hardware with capability score for the first time");
+                hardware::detect_all_with_score()
+                    .await
+                    .map(Arc::new)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .map(Arc::clone)
+    }
 }
 
 /// Detect hardware or return cached results
@@ -65,3 +92,59 @@ pub async fn get_cached_hardware(
 ) -> Result<Option<HardwareInfo>, String> {
     Ok(cache.get().map(Arc::unwrap_or_clone))
 }
+
+/// Detect hardware including the synthetic CPU/memory-bandwidth capability
+/// score, or return the cached scored result.
+/// Uses get_or_detect_with_score to ensure the micro-benchmark runs at most
+/// once even with concurrent requests.
+#[tauri::command]
+pub async fn detect_hardware_with_score(
+    cache: tauri::State<'_, HardwareCache>,
+) -> Result<HardwareInfo, String> {
+    let info = cache.get_or_detect_with_score().await?;
+    Ok(Arc::unwrap_or_clone(info))
+}
+
+/// Start the background hardware telemetry sampler, emitting
+/// `hardware://telemetry` events at `interval_ms` (default 1000ms). A no-op
+/// if the sampler is already running.
+///
+/// Looks up the GPU vendor from `cache` (triggering detection if it hasn't
+/// run yet) so telemetry sampling dispatches to the right GPU backend; falls
+/// back to `GpuVendor::Unknown` (CPU/memory/disk telemetry only) if no GPU
+/// was detected.
+#[tauri::command]
+pub async fn hardware_monitor_start(
+    app: tauri::AppHandle,
+    monitor: tauri::State<'_, HardwareMonitor>,
+    cache: tauri::State<'_, HardwareCache>,
+    interval_ms: Option<u64>,
+) -> Result<(), String> {
+    let gpu_vendor = cache
+        .get_or_detect()
+        .await?
+        .gpus
+        .first()
+        .map(|gpu| gpu.vendor.clone())
+        .unwrap_or(GpuVendor::Unknown);
+
+    let interval = Duration::from_millis(interval_ms.unwrap_or(1000));
+    monitor.start(app, interval, gpu_vendor);
+    Ok(())
+}
+
+/// Stop the background hardware telemetry sampler. Safe to call when not running.
+#[tauri::command]
+pub fn hardware_monitor_stop(monitor: tauri::State<'_, HardwareMonitor>) {
+    monitor.stop();
+}
+
+/// Recent telemetry samples collected by the background sampler (oldest
+/// first), for the frontend to render rolling graphs without having missed
+/// any `hardware://telemetry` events emitted before it started listening.
+#[tauri::command]
+pub fn hardware_monitor_history(
+    monitor: tauri::State<'_, HardwareMonitor>,
+) -> Vec<TelemetrySnapshot> {
+    monitor.history()
+}
@@ -3,15 +3,53 @@
 //! This module provides:
 //! - `LibreOfficeState`: Managed state wrapper around MCPClient
 //! - Tauri commands for connecting, listing tools, and calling tools
-//! - Auto-recovery on crash (one retry)
+//! - Auto-recovery on crash: a dead MCP server is respawned, re-handshaked,
+//!   and the failed tool call retried once, with connection-state changes
+//!   surfaced to the frontend as events (see `ConnectionState`)
 
 use crate::libreoffice::{MCPClient, Tool, LibreOfficeError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
-use tauri::State;
+use std::time::Duration;
+use tauri::{Emitter, State};
 use tokio::sync::Mutex;
 
+/// Tauri event emitted on every `ConnectionState` transition during a reconnect.
+const CONNECTION_STATE_EVENT: &str = "libreoffice://connection-state";
+
+/// Connection-state transitions a reconnect attempt goes through, emitted as
+/// [`CONNECTION_STATE_EVENT`] so the frontend can show reconnect progress
+/// instead of just a raw tool-call error.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Disconnected,
+    Reconnecting,
+    Connected,
+}
+
+/// Retry/backoff knobs for [`LibreOfficeState::reconnect`]. Mirrors the shape
+/// of `process_manager::RestartPolicy`, but governs respawning the whole
+/// `MCPClient` (process + handshake) from the command layer, rather than the
+/// process-level restart that policy caps.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectConfig {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
 // ============================================================================
 // State Management
 // ============================================================================
@@ -20,37 +58,127 @@ use tokio::sync::Mutex;
 ///
 /// Uses lazy initialization - client is only created on first `connect()` call.
 /// Thread-safe with tokio::sync::Mutex for async operations.
+///
+/// The client itself is `Arc`-wrapped so commands can clone it out of the slot
+/// and release this outer lock before awaiting a tool call. `MCPClient` already
+/// multiplexes concurrent requests internally (see `transport.rs`'s
+/// `pending_requests` map), so holding this lock across a `call_tool().await`
+/// would serialize unrelated commands (e.g. a slow tool blocking
+/// `libreoffice_status`) for no reason - this lock only ever guards the
+/// connect/disconnect/reconnect decision, never an in-flight request.
 pub struct LibreOfficeState {
-    client: Arc<Mutex<Option<MCPClient>>>,
+    client: Arc<Mutex<Option<Arc<MCPClient>>>>,
+    reconnect_config: ReconnectConfig,
 }
 
 impl Default for LibreOfficeState {
     fn default() -> Self {
         Self {
             client: Arc::new(Mutex::new(None)),
+            reconnect_config: ReconnectConfig::default(),
         }
     }
 }
 
 impl LibreOfficeState {
-    /// Get the MCP client, returning error if not connected
-    async fn get_client(&self) -> Result<tokio::sync::MutexGuard<'_, Option<MCPClient>>, LibreOfficeError> {
+    /// Override the default retry count/backoff used by [`Self::reconnect`].
+    pub fn with_reconnect_config(mut self, reconnect_config: ReconnectConfig) -> Self {
+        self.reconnect_config = reconnect_config;
+        self
+    }
+
+    /// Clone out the connected client, returning an error if not connected.
+    /// Dropping the returned `Arc` (and the lock this takes only briefly) has
+    /// no effect on other holders, so callers should await tool calls on the
+    /// clone rather than under `self.client`'s lock.
+    async fn get_client(&self) -> Result<Arc<MCPClient>, LibreOfficeError> {
         let guard = self.client.lock().await;
-        if guard.is_none() {
-            return Err(LibreOfficeError::NotInitialized);
-        }
-        Ok(guard)
+        guard.clone().ok_or(LibreOfficeError::NotInitialized)
     }
 
     /// Check if client is connected and running
     async fn is_connected(&self) -> bool {
-        let guard = self.client.lock().await;
-        if let Some(client) = guard.as_ref() {
+        let client = self.client.lock().await.clone();
+        if let Some(client) = client {
             client.is_running().await
         } else {
             false
         }
     }
+
+    /// Respawn the MCP server and re-run the handshake, with capped
+    /// exponential backoff between attempts, emitting each connection-state
+    /// transition via [`CONNECTION_STATE_EVENT`]. On success, the freshly
+    /// connected client replaces the stored one.
+    async fn reconnect(&self, app: &tauri::AppHandle) -> Result<Arc<MCPClient>, LibreOfficeError> {
+        emit_connection_state(app, ConnectionState::Disconnected);
+
+        let mut backoff = self.reconnect_config.initial_backoff;
+        let mut last_err = None;
+
+        for attempt in 1..=self.reconnect_config.max_attempts {
+            emit_connection_state(app, ConnectionState::Reconnecting);
+            log::warn!(
+                "Reconnecting to MCP server (attempt {attempt}/{})",
+                self.reconnect_config.max_attempts
+            );
+
+            match MCPClient::new().await {
+                Ok(client) => {
+                    let client = Arc::new(client);
+                    *self.client.lock().await = Some(Arc::clone(&client));
+                    emit_connection_state(app, ConnectionState::Connected);
+                    return Ok(client);
+                }
+                Err(e) => {
+                    log::warn!("Reconnect attempt {attempt} failed: {e}");
+                    last_err = Some(e);
+                    if attempt < self.reconnect_config.max_attempts {
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(self.reconnect_config.max_backoff);
+                    }
+                }
+            }
+        }
+
+        emit_connection_state(app, ConnectionState::Disconnected);
+        Err(last_err.unwrap_or(LibreOfficeError::NotInitialized))
+    }
+
+    /// Call a tool, transparently reconnecting and retrying once if the
+    /// failure looks like a dead server (a transport I/O error, or the
+    /// process no longer running) rather than a normal tool-level error.
+    async fn call_tool_supervised(
+        &self,
+        app: &tauri::AppHandle,
+        tool_name: &str,
+        arguments: Value,
+    ) -> Result<Value, LibreOfficeError> {
+        let client = self.get_client().await?;
+
+        match client.call_tool(tool_name, arguments.clone()).await {
+            Ok(result) => return Ok(result),
+            Err(e) if looks_like_crash(&client, &e).await => {
+                log::warn!("Tool call '{tool_name}' failed ({e}), reconnecting and retrying once");
+            }
+            Err(e) => return Err(e),
+        }
+
+        let client = self.reconnect(app).await?;
+        client.call_tool(tool_name, arguments).await
+    }
+}
+
+/// Whether a failed `call_tool` looks like it was caused by the server
+/// process dying, rather than a normal tool-level error response.
+async fn looks_like_crash(client: &MCPClient, error: &LibreOfficeError) -> bool {
+    matches!(error, LibreOfficeError::IoError(_)) || !client.is_running().await
+}
+
+fn emit_connection_state(app: &tauri::AppHandle, state: ConnectionState) {
+    if let Err(e) = app.emit(CONNECTION_STATE_EVENT, state) {
+        log::warn!("Failed to emit LibreOffice connection-state event: {e}");
+    }
 }
 
 // ============================================================================
@@ -119,10 +247,11 @@ pub async fn libreoffice_connect(
 ) -> Result<StatusResponse, String> {
     log::info!("libreoffice_connect called");
 
-    let mut guard = state.client.lock().await;
-
-    // Check if already connected
-    if let Some(client) = guard.as_ref() {
+    // Check if already connected, without holding the lock across the
+    // `is_running().await` below (a second concurrent `connect` would
+    // otherwise queue behind it for no reason).
+    let existing = state.client.lock().await.clone();
+    if let Some(client) = existing {
         if client.is_running().await {
             let info = client.server_info();
             return Ok(StatusResponse {
@@ -144,7 +273,7 @@ pub async fn libreoffice_connect(
                 server_name: Some(info.server_info.name.clone()),
                 server_version: Some(info.server_info.version.clone()),
             };
-            *guard = Some(client);
+            *state.client.lock().await = Some(Arc::new(client));
             log::info!("LibreOffice MCP client connected successfully");
             Ok(response)
         }
@@ -162,9 +291,9 @@ pub async fn libreoffice_disconnect(
 ) -> Result<(), String> {
     log::info!("libreoffice_disconnect called");
 
-    let mut guard = state.client.lock().await;
+    let client = state.client.lock().await.take();
 
-    if let Some(client) = guard.take() {
+    if let Some(client) = client {
         if let Err(e) = client.shutdown().await {
             log::warn!("Error during shutdown: {}", e);
         }
@@ -181,9 +310,9 @@ pub async fn libreoffice_status(
 ) -> Result<StatusResponse, String> {
     log::info!(">>> libreoffice_status command called");
     println!(">>> libreoffice_status command called (println)");
-    let guard = state.client.lock().await;
+    let client = state.client.lock().await.clone();
 
-    if let Some(client) = guard.as_ref() {
+    if let Some(client) = client {
         if client.is_running().await {
             let info = client.server_info();
             return Ok(StatusResponse {
@@ -209,14 +338,11 @@ pub async fn libreoffice_list_tools(
     log::info!("libreoffice_list_tools called");
     println!(">>> libreoffice_list_tools called");
 
-    let guard = state.client.lock().await;
-    println!(">>> Got lock");
-
-    let client = guard.as_ref()
-        .ok_or_else(|| {
-            println!(">>> Client not initialized!");
-            LibreOfficeError::NotInitialized.to_string()
-        })?;
+    let client = state.get_client().await.map_err(|e| {
+        println!(">>> Client not initialized!");
+        e.to_string()
+    })?;
+    println!(">>> Got client");
 
     println!(">>> Calling client.list_tools()...");
     let tools = client.list_tools().await
@@ -234,18 +360,14 @@ pub async fn libreoffice_list_tools(
 /// This is the generic tool call command - works with any tool.
 #[tauri::command]
 pub async fn libreoffice_call_tool(
+    app: tauri::AppHandle,
     state: State<'_, LibreOfficeState>,
     tool_name: String,
     arguments: Value,
 ) -> Result<ToolCallResponse, String> {
     log::info!("libreoffice_call_tool called: {}", tool_name);
 
-    let guard = state.client.lock().await;
-
-    let client = guard.as_ref()
-        .ok_or_else(|| LibreOfficeError::NotInitialized.to_string())?;
-
-    match client.call_tool(&tool_name, arguments).await {
+    match state.call_tool_supervised(&app, &tool_name, arguments).await {
         Ok(result) => {
             log::info!("Tool {} completed successfully", tool_name);
             Ok(ToolCallResponse {
@@ -274,6 +396,7 @@ pub async fn libreoffice_call_tool(
 /// Convenience wrapper for `create_blank_document` tool.
 #[tauri::command]
 pub async fn libreoffice_create_document(
+    app: tauri::AppHandle,
     state: State<'_, LibreOfficeState>,
     filename: String,
     title: Option<String>,
@@ -281,11 +404,6 @@ pub async fn libreoffice_create_document(
 ) -> Result<ToolCallResponse, String> {
     log::info!("libreoffice_create_document called: {}", filename);
 
-    let guard = state.client.lock().await;
-
-    let client = guard.as_ref()
-        .ok_or_else(|| LibreOfficeError::NotInitialized.to_string())?;
-
     let mut args = serde_json::json!({
         "filename": filename
     });
@@ -297,7 +415,10 @@ pub async fn libreoffice_create_document(
         args["doc_type"] = Value::String(dt);
     }
 
-    match client.call_tool("create_blank_document", args).await {
+    match state
+        .call_tool_supervised(&app, "create_blank_document", args)
+        .await
+    {
         Ok(result) => {
             log::info!("Document {} created successfully", filename);
             Ok(ToolCallResponse {
@@ -322,21 +443,17 @@ pub async fn libreoffice_create_document(
 /// Convenience wrapper for `add_text` tool.
 #[tauri::command]
 pub async fn libreoffice_add_text(
+    app: tauri::AppHandle,
     state: State<'_, LibreOfficeState>,
     text: String,
 ) -> Result<ToolCallResponse, String> {
     log::info!("libreoffice_add_text called");
 
-    let guard = state.client.lock().await;
-
-    let client = guard.as_ref()
-        .ok_or_else(|| LibreOfficeError::NotInitialized.to_string())?;
-
     let args = serde_json::json!({
         "text": text
     });
 
-    match client.call_tool("add_text", args).await {
+    match state.call_tool_supervised(&app, "add_text", args).await {
         Ok(result) => Ok(ToolCallResponse {
             success: true,
             result: Some(result),
@@ -355,23 +472,19 @@ pub async fn libreoffice_add_text(
 /// Convenience wrapper for `save_document` tool.
 #[tauri::command]
 pub async fn libreoffice_save_document(
+    app: tauri::AppHandle,
     state: State<'_, LibreOfficeState>,
     path: Option<String>,
 ) -> Result<ToolCallResponse, String> {
     log::info!("libreoffice_save_document called");
 
-    let guard = state.client.lock().await;
-
-    let client = guard.as_ref()
-        .ok_or_else(|| LibreOfficeError::NotInitialized.to_string())?;
-
     let args = if let Some(p) = path {
         serde_json::json!({ "path": p })
     } else {
         serde_json::json!({})
     };
 
-    match client.call_tool("save_document", args).await {
+    match state.call_tool_supervised(&app, "save_document", args).await {
         Ok(result) => Ok(ToolCallResponse {
             success: true,
             result: Some(result),
@@ -383,4 +496,24 @@ pub async fn libreoffice_save_document(
             error: Some(e.to_string()),
         }),
     }
+}
+
+/// Run a Lua macro that chains several MCP tool calls together via the bound
+/// `office` object (e.g. `office:call("add_text", { text = "Hello" })`).
+///
+/// Returns a transcript of every step attempted, so the frontend can show
+/// what happened even when a later step fails or the script aborts partway
+/// through - see `libreoffice::script` for the failure-reporting contract.
+#[cfg(feature = "scripting")]
+#[tauri::command]
+pub async fn libreoffice_run_script(
+    state: State<'_, LibreOfficeState>,
+    source: String,
+) -> Result<crate::libreoffice::script::ScriptTranscript, String> {
+    log::info!("libreoffice_run_script called");
+
+    let client = state.get_client().await.map_err(|e| e.to_string())?;
+    crate::libreoffice::script::run_script(client, source)
+        .await
+        .map_err(|e| e.to_string())
 }
\ No newline at end of file
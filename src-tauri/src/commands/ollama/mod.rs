@@ -0,0 +1,440 @@
+mod transport;
+
+use super::errors::Error;
+use crate::security;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::broadcast;
+
+const DEFAULT_TCP_URL: &str = "http://localhost:11434";
+
+// Student-friendly system prompt for coding assistance
+const SYSTEM_PROMPT: &str = r"You are a helpful coding assistant designed for secondary school students (ages 11-18).
+Your goal is to explain programming concepts clearly and provide well-commented code examples.
+
+Guidelines:
+- Use simple, encouraging language
+- Break down complex concepts into steps
+- Always include helpful comments in code
+- Be patient and supportive
+- Adapt explanations to the student's level
+- Encourage learning and experimentation";
+
+/// Shared HTTP client for connection pooling
+pub struct HttpClient {
+    client: reqwest::Client,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl HttpClient {
+    pub fn get(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+/// Where the Ollama server is reachable.
+///
+/// Most setups run Ollama on its default loopback TCP port, but some
+/// privacy-focused deployments run it behind a Unix domain socket instead,
+/// with no TCP listener at all - stricter than a loopback port since it has
+/// no network stack to misconfigure. `reqwest` has no built-in support for
+/// Unix sockets, so that variant is spoken over a hand-rolled HTTP/1.1
+/// client (see [`transport`]) instead of `HttpClient`.
+#[derive(Debug, Clone)]
+pub enum OllamaEndpoint {
+    Tcp(String),
+    UnixSocket(PathBuf),
+}
+
+/// Configuration for how to reach the Ollama server
+#[derive(Clone)]
+pub struct OllamaConfig {
+    endpoint: OllamaEndpoint,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        // Read from environment variable or use default
+        let raw = env::var("OLLAMA_URL").unwrap_or_else(|_| DEFAULT_TCP_URL.to_string());
+
+        // A `unix://` prefix selects the socket transport; validate the
+        // socket path with the same allowlist discipline `validate_path`
+        // uses. Anything else is treated as a TCP URL and validated as
+        // localhost-only, same as before.
+        let endpoint = if let Some(socket_path) = raw.strip_prefix("unix://") {
+            security::validate_socket_path(socket_path)
+                .map(OllamaEndpoint::UnixSocket)
+                .unwrap_or_else(|err| {
+                    log::error!("{err}");
+                    log::warn!("Falling back to default: {DEFAULT_TCP_URL}");
+                    OllamaEndpoint::Tcp(DEFAULT_TCP_URL.to_string())
+                })
+        } else {
+            security::validate_ollama_url(&raw)
+                .map(OllamaEndpoint::Tcp)
+                .unwrap_or_else(|err| {
+                    log::error!("{err}");
+                    log::warn!("Falling back to default: {DEFAULT_TCP_URL}");
+                    OllamaEndpoint::Tcp(DEFAULT_TCP_URL.to_string())
+                })
+        };
+
+        Self { endpoint }
+    }
+}
+
+impl OllamaConfig {
+    pub fn endpoint(&self) -> &OllamaEndpoint {
+        &self.endpoint
+    }
+
+    /// The loopback base URL, for callers that only ever speak TCP (e.g. the
+    /// benchmark suite, which also needs the OS process behind the port).
+    ///
+    /// # Errors
+    /// Returns an error if this config targets a Unix socket instead.
+    pub fn base_url(&self) -> Result<&str, String> {
+        match &self.endpoint {
+            OllamaEndpoint::Tcp(url) => Ok(url),
+            OllamaEndpoint::UnixSocket(path) => Err(format!(
+                "This operation requires a TCP endpoint, but Ollama is configured over a Unix socket at {path:?}"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OllamaMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OllamaRequest {
+    pub model: String,
+    pub messages: Vec<OllamaMessage>,
+    pub stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OllamaResponse {
+    pub message: Option<OllamaMessage>,
+    pub done: bool,
+    // Token count metadata (only present when done=true)
+    pub eval_count: Option<usize>,        // Number of tokens in the response
+    // Timing metadata (only present when done=true)
+    pub total_duration: Option<u64>,      // Total time in nanoseconds
+    pub prompt_eval_duration: Option<u64>, // Prompt evaluation time in nanoseconds
+    pub eval_duration: Option<u64>,       // Response generation time in nanoseconds
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OllamaModel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OllamaModelsResponse {
+    pub models: Vec<OllamaModel>,
+}
+
+/// Global state to manage stream cancellation
+pub struct StreamCancellation {
+    sender: Mutex<Option<broadcast::Sender<()>>>,
+}
+
+impl Default for StreamCancellation {
+    fn default() -> Self {
+        Self {
+            sender: Mutex::new(None),
+        }
+    }
+}
+
+impl StreamCancellation {
+    pub fn create_channel(&self) -> broadcast::Receiver<()> {
+        let mut sender_lock = self.sender.lock()
+            .expect("StreamCancellation mutex poisoned - indicates panic in stream handler");
+        let (tx, rx) = broadcast::channel(1); 
+        *sender_lock = Some(tx); // Transmitter stored globally for cancellation
+        rx // Return receiver for this stream
+    }
+
+    pub fn cancel(&self) {
+        let sender_lock = self.sender.lock() // 
+            .expect("StreamCancellation mutex poisoned - indicates panic in stream handler");
+        if let Some(sender) = sender_lock.as_ref() {
+            let _ = sender.send(());
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut sender_lock = self.sender.lock()
+            .expect("StreamCancellation mutex poisoned - indicates panic in stream handler");
+        *sender_lock = None;
+    }
+}
+
+/// Check if Ollama server is running and available
+#[tauri::command]
+pub async fn check_ollama(
+    client: State<'_, HttpClient>,
+    config: State<'_, OllamaConfig>,
+) -> Result<bool, Error> {
+    match config.endpoint() {
+        OllamaEndpoint::Tcp(base_url) => {
+            let url = format!("{base_url}/api/tags");
+            let response = client.get().get(&url).send().await;
+
+            match response {
+                Ok(resp) => Ok(resp.status().is_success()),
+                Err(_) => Ok(false),
+            }
+        }
+        OllamaEndpoint::UnixSocket(socket_path) => {
+            match transport::request(socket_path, "GET", "/api/tags", None).await {
+                Ok(resp) => Ok((200..300).contains(&resp.status)),
+                Err(_) => Ok(false),
+            }
+        }
+    }
+}
+
+/// Get list of available Ollama models
+#[tauri::command]
+pub async fn get_ollama_models(
+    client: State<'_, HttpClient>,
+    config: State<'_, OllamaConfig>,
+) -> Result<Vec<String>, Error> {
+    let body = match config.endpoint() {
+        OllamaEndpoint::Tcp(base_url) => {
+            let url = format!("{base_url}/api/tags");
+            client
+                .get()
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| Error::Other(format!("Failed to connect to Ollama: {e}")))?
+                .bytes()
+                .await
+                .map_err(|e| Error::Other(format!("Failed to read Ollama response: {e}")))?
+                .to_vec()
+        }
+        OllamaEndpoint::UnixSocket(socket_path) => {
+            transport::request(socket_path, "GET", "/api/tags", None)
+                .await
+                .map_err(|e| Error::Other(format!("Failed to connect to Ollama: {e}")))?
+                .body
+        }
+    };
+
+    let models: OllamaModelsResponse = serde_json::from_slice(&body)
+        .map_err(|e| Error::Other(format!("Failed to parse models: {e}")))?;
+
+    Ok(models.models.into_iter().map(|m| m.name).collect())
+}
+
+/// Cancel ongoing generation
+#[tauri::command]
+pub fn cancel_generation(cancellation: State<StreamCancellation>) {
+    cancellation.cancel();
+}
+
+/// Generate streaming response from Ollama
+#[tauri::command]
+pub async fn generate_stream(
+    app_handle: AppHandle,
+    prompt: String,
+    model: String,
+    context: Option<Vec<OllamaMessage>>,
+    client: State<'_, HttpClient>,
+    config: State<'_, OllamaConfig>,
+    cancellation: State<'_, StreamCancellation>,
+) -> Result<(), Error> {
+    // Create a new cancellation receiver for this stream
+    let mut cancel_rx = cancellation.create_channel();
+
+    // Build messages array with system prompt, context, and current prompt
+    let mut messages = vec![OllamaMessage {
+        role: "system".to_string(),
+        content: SYSTEM_PROMPT.to_string(),
+    }];
+
+    // Add context messages if provided
+    if let Some(ctx) = context {
+        messages.extend(ctx);
+    }
+
+    // Add current user prompt
+    messages.push(OllamaMessage {
+        role: "user".to_string(),
+        content: prompt,
+    });
+
+    let request = OllamaRequest {
+        model,
+        messages,
+        stream: true,
+    };
+
+    match config.endpoint() {
+        OllamaEndpoint::Tcp(base_url) => {
+            generate_stream_tcp(app_handle, base_url, &request, client.get(), &cancellation, cancel_rx).await
+        }
+        OllamaEndpoint::UnixSocket(socket_path) => {
+            generate_stream_unix(app_handle, socket_path, &request, &cancellation).await
+        }
+    }
+}
+
+/// Stream a chat response over the TCP/`reqwest` transport, emitting one
+/// `ollama_chunk` event per line of the NDJSON response as it arrives.
+async fn generate_stream_tcp(
+    app_handle: AppHandle,
+    base_url: &str,
+    request: &OllamaRequest,
+    client: &reqwest::Client,
+    cancellation: &StreamCancellation,
+    mut cancel_rx: broadcast::Receiver<()>,
+) -> Result<(), Error> {
+    let url = format!("{base_url}/api/chat");
+    let response = client
+        .post(&url)
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to send request: {e}")))?;
+
+    let mut stream = response.bytes_stream();
+
+    loop {
+        tokio::select! {
+            // Check for cancellation
+            _ = cancel_rx.recv() => {
+                // Stream was cancelled
+                cancellation.clear();
+                if let Err(e) = app_handle.emit("ollama_cancelled", ()) {
+                    log::debug!("Failed to emit cancellation event (frontend may be closed): {e}");
+                }
+                return Ok(());
+            }
+            // Process stream chunks
+            chunk_result = stream.next() => {
+                match chunk_result {
+                    Some(Ok(bytes)) => {
+                        if let Ok(text) = String::from_utf8(bytes.to_vec()) {
+                            for line in text.lines() {
+                                if let Some(result) = emit_ollama_line(&app_handle, cancellation, line) {
+                                    return result;
+                                }
+                            }
+                        }
+                    }
+                    Some(Err(e)) => {
+                        cancellation.clear();
+                        if let Err(emit_err) = app_handle.emit("ollama_error", format!("Stream error: {e}")) {
+                            log::debug!("Failed to emit error event (frontend may be closed): {emit_err}");
+                        }
+                        return Err(Error::Other(format!("Stream error: {e}")));
+                    }
+                    None => {
+                        // Stream ended
+                        cancellation.clear();
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Stream a chat response over the Unix-socket transport.
+///
+/// The raw HTTP/1.1 client in [`transport`] frames the response by
+/// `Content-Length`, so unlike [`generate_stream_tcp`] the full body arrives
+/// in one piece rather than incrementally - there's no partial response to
+/// cancel mid-flight. Once it arrives, it's split into NDJSON lines and each
+/// is emitted exactly like the TCP path, so the frontend sees the same
+/// sequence of `ollama_chunk`/`ollama_done` events either way.
+async fn generate_stream_unix(
+    app_handle: AppHandle,
+    socket_path: &std::path::Path,
+    request: &OllamaRequest,
+    cancellation: &StreamCancellation,
+) -> Result<(), Error> {
+    let body = serde_json::to_vec(request)
+        .map_err(|e| Error::Other(format!("Failed to serialize request: {e}")))?;
+
+    let response = match transport::request(socket_path, "POST", "/api/chat", Some(&body)).await {
+        Ok(response) => response,
+        Err(e) => {
+            cancellation.clear();
+            if let Err(emit_err) = app_handle.emit("ollama_error", format!("Stream error: {e}")) {
+                log::debug!("Failed to emit error event (frontend may be closed): {emit_err}");
+            }
+            return Err(Error::Other(format!("Stream error: {e}")));
+        }
+    };
+
+    let text = String::from_utf8_lossy(&response.body);
+    for line in text.lines() {
+        if let Some(result) = emit_ollama_line(&app_handle, cancellation, line) {
+            return result;
+        }
+    }
+
+    cancellation.clear();
+    Ok(())
+}
+
+/// Parse one line of the NDJSON chat stream and emit the corresponding
+/// frontend event. Returns `Some(result)` when the stream should stop
+/// (content emitted to a closed frontend, or `done: true`), `None` to keep
+/// processing further lines.
+fn emit_ollama_line(
+    app_handle: &AppHandle,
+    cancellation: &StreamCancellation,
+    line: &str,
+) -> Option<Result<(), Error>> {
+    if line.trim().is_empty() {
+        return None;
+    }
+
+    match serde_json::from_str::<OllamaResponse>(line) {
+        Ok(response) => {
+            if let Some(message) = response.message {
+                if let Err(e) = app_handle.emit("ollama_chunk", message.content) {
+                    log::debug!("Frontend disconnected during stream, stopping: {e}");
+                    cancellation.clear();
+                    return Some(Ok(()));
+                }
+            }
+
+            if response.done {
+                cancellation.clear();
+                if let Err(e) = app_handle.emit("ollama_done", ()) {
+                    log::debug!("Failed to emit done event (frontend may be closed): {e}");
+                }
+                return Some(Ok(()));
+            }
+
+            None
+        }
+        Err(e) => {
+            log::warn!("Failed to parse Ollama response: {e} | Line: {line}");
+            // Continue processing other lines - don't fail entire stream
+            None
+        }
+    }
+}
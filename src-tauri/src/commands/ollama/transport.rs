@@ -0,0 +1,136 @@
+//! Minimal HTTP/1.1 client for talking to Ollama over a Unix domain socket.
+//!
+//! `reqwest` has no built-in Unix-socket support in this codebase, so when
+//! [`super::OllamaEndpoint::UnixSocket`] is configured, requests are framed
+//! and parsed by hand over a `tokio::net::UnixStream`: a request line plus
+//! `Host` and `Content-Length` headers on the way out, and a status line
+//! plus `Content-Length` parsed from the response on the way in.
+
+use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+/// A fully-framed HTTP/1.1 response read off a Unix socket.
+pub struct UnixHttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// Send a single HTTP/1.1 request over `socket_path` and read back a
+/// fully-framed response.
+///
+/// Opens a fresh connection per request rather than pooling, mirroring how
+/// little machinery the equivalent `reqwest::Client` call sites need for a
+/// single request/response round trip.
+pub async fn request(
+    socket_path: &Path,
+    method: &str,
+    path: &str,
+    body: Option<&[u8]>,
+) -> Result<UnixHttpResponse, String> {
+    let mut stream = UnixStream::connect(socket_path)
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama Unix socket {socket_path:?}: {e}"))?;
+
+    write_request(&mut stream, method, path, body).await?;
+    read_response(&mut stream).await
+}
+
+/// Write the request line, `Host`, and `Content-Length` headers, then the
+/// body, to `stream`.
+async fn write_request(
+    stream: &mut UnixStream,
+    method: &str,
+    path: &str,
+    body: Option<&[u8]>,
+) -> Result<(), String> {
+    let body = body.unwrap_or(&[]);
+    let mut request = format!(
+        "{method} {path} HTTP/1.1\r\n\
+         Host: localhost\r\n\
+         Content-Length: {}\r\n\
+         Content-Type: application/json\r\n\
+         Connection: close\r\n\
+         \r\n",
+        body.len()
+    )
+    .into_bytes();
+    request.extend_from_slice(body);
+
+    stream
+        .write_all(&request)
+        .await
+        .map_err(|e| format!("Failed to write request to Ollama Unix socket: {e}"))
+}
+
+/// Read a full HTTP/1.1 response: the status line and headers (to find
+/// `Content-Length`), then exactly that many body bytes.
+async fn read_response(stream: &mut UnixStream) -> Result<UnixHttpResponse, String> {
+    let mut buf = Vec::new();
+    let header_end = loop {
+        let mut chunk = [0u8; 4096];
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| format!("Failed to read response from Ollama Unix socket: {e}"))?;
+        if n == 0 {
+            return Err(
+                "Ollama Unix socket closed before sending a complete response header".to_string(),
+            );
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_header_end(&buf) {
+            break pos;
+        }
+    };
+
+    let header_text = String::from_utf8_lossy(&buf[..header_end]);
+    let mut lines = header_text.split("\r\n");
+    let status_line = lines
+        .next()
+        .ok_or("Empty HTTP response from Ollama Unix socket")?;
+    let status = parse_status_code(status_line)?;
+
+    let content_length = lines
+        .find_map(|line| {
+            line.split_once(':')
+                .filter(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .map(|(_, value)| value.trim())
+        })
+        .ok_or("Ollama Unix socket response has no Content-Length header")?
+        .parse::<usize>()
+        .map_err(|e| format!("Malformed Content-Length header: {e}"))?;
+
+    let body_start = header_end + 4; // skip the "\r\n\r\n" separator
+    let mut body = buf[body_start..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = [0u8; 4096];
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| format!("Failed to read response body from Ollama Unix socket: {e}"))?;
+        if n == 0 {
+            return Err(
+                "Ollama Unix socket closed before sending the full response body".to_string(),
+            );
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(UnixHttpResponse { status, body })
+}
+
+/// Find the `\r\n\r\n` that separates headers from body, if it's arrived yet.
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn parse_status_code(status_line: &str) -> Result<u16, String> {
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| format!("Malformed HTTP status line from Ollama Unix socket: {status_line}"))?
+        .parse::<u16>()
+        .map_err(|e| format!("Malformed HTTP status code from Ollama Unix socket: {e}"))
+}
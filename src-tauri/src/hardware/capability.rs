@@ -0,0 +1,119 @@
+//! Synthetic CPU and memory-bandwidth capability scoring.
+//!
+//! Produces a normalized score against a fixed reference machine so
+//! inference numbers collected on heterogeneous test hardware can be
+//! compared on equal footing - a score of ~1.0 means "reference-class".
+//! Unlike the rest of hardware detection, this runs an actual timed
+//! micro-benchmark rather than reading static attributes, so it's kept
+//! behind [`crate::commands::hardware::HardwareCache::get_or_detect_with_score`]
+//! rather than the default `get_or_detect` path that every request can
+//! afford to pay for.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Wall-clock budget for the CPU micro-benchmark.
+const CPU_BENCHMARK_BUDGET: Duration = Duration::from_millis(200);
+
+/// Buffer hashed repeatedly during the CPU micro-benchmark.
+const CPU_BENCHMARK_BUFFER_SIZE: usize = 4096;
+
+/// Reference machine's completed hash rounds within `CPU_BENCHMARK_BUDGET`
+/// (measured once on a mid-range desktop CPU), used as the "1.0" baseline.
+const REFERENCE_CPU_ROUNDS: f64 = 1_200_000.0;
+
+/// Buffer size for the memory-bandwidth micro-benchmark (sequential copy).
+const MEMORY_BENCHMARK_BUFFER_BYTES: usize = 64 * 1024 * 1024;
+
+/// Reference machine's measured sequential-copy bandwidth (GB/s), used as
+/// the "1.0" baseline for `memory_score`.
+const REFERENCE_MEMORY_GBPS: f64 = 12.0;
+
+/// Normalized CPU and memory-bandwidth capability scores, relative to a
+/// fixed reference machine (~1.0 means "reference-class").
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CapabilityScore {
+    /// Completed hash rounds in a fixed wall-clock budget, normalized
+    /// against the reference machine.
+    pub cpu_score: f64,
+    /// Sequential-copy bandwidth (GB/s), normalized against the reference
+    /// machine.
+    pub memory_score: f64,
+}
+
+/// Run both micro-benchmarks and normalize against the reference machine.
+/// Takes on the order of `CPU_BENCHMARK_BUDGET` plus the time to copy
+/// `MEMORY_BENCHMARK_BUFFER_BYTES` - cheap enough to run once per process,
+/// too slow to run on every hardware detection call.
+pub fn measure() -> CapabilityScore {
+    CapabilityScore {
+        cpu_score: measure_cpu_score(),
+        memory_score: measure_memory_score(),
+    }
+}
+
+/// Repeatedly hash a small buffer for a fixed wall-clock budget and count
+/// completed rounds, normalized against the reference machine's round
+/// count.
+fn measure_cpu_score() -> f64 {
+    let mut buffer = [0u8; CPU_BENCHMARK_BUFFER_SIZE];
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        *byte = (i % 256) as u8;
+    }
+
+    let start = Instant::now();
+    let mut rounds: u64 = 0;
+    let mut hash: u64 = 0xcbf29ce484222325; // FNV-1a offset basis
+
+    while start.elapsed() < CPU_BENCHMARK_BUDGET {
+        for &byte in &buffer {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3); // FNV-1a prime
+        }
+        rounds += 1;
+        std::hint::black_box(hash);
+    }
+
+    rounds as f64 / REFERENCE_CPU_ROUNDS
+}
+
+/// Time a large sequential buffer copy and report the measured bandwidth,
+/// normalized against the reference machine's bandwidth.
+fn measure_memory_score() -> f64 {
+    let src = vec![0xABu8; MEMORY_BENCHMARK_BUFFER_BYTES];
+    let mut dst = vec![0u8; MEMORY_BENCHMARK_BUFFER_BYTES];
+
+    let start = Instant::now();
+    dst.copy_from_slice(&src);
+    let elapsed = start.elapsed();
+    std::hint::black_box(&dst);
+
+    let gb_copied = MEMORY_BENCHMARK_BUFFER_BYTES as f64 / 1_000_000_000.0;
+    let gbps = gb_copied / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    gbps / REFERENCE_MEMORY_GBPS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_returns_positive_scores() {
+        let score = measure();
+        assert!(score.cpu_score > 0.0, "CPU score should be positive");
+        assert!(score.memory_score > 0.0, "memory score should be positive");
+    }
+
+    #[test]
+    fn test_measure_cpu_score_is_finite() {
+        let score = measure_cpu_score();
+        assert!(score.is_finite());
+    }
+
+    #[test]
+    fn test_measure_memory_score_is_finite() {
+        let score = measure_memory_score();
+        assert!(score.is_finite());
+    }
+}
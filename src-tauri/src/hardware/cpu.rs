@@ -1,4 +1,4 @@
-use crate::hardware::types::{CpuFeatures, CpuInfo};
+use crate::hardware::types::{CpuFeatures, CpuInfo, Microarchitecture};
 use sysinfo::System;
 
 /// Detect CPU information (cross-platform)
@@ -73,14 +73,37 @@ fn detect_x86(
         avx2: is_x86_feature_detected!("avx2"),
         avx512f: is_x86_feature_detected!("avx512f"),
         fma: is_x86_feature_detected!("fma"),
+        // x86 matrix/low-precision extensions (AI-relevant: quantized/bf16 inference)
+        avx512_vnni: is_x86_feature_detected!("avx512vnni"),
+        avx512_bf16: is_x86_feature_detected!("avx512bf16"),
+        amx_tile: is_x86_feature_detected!("amx-tile"),
+        amx_int8: is_x86_feature_detected!("amx-int8"),
+        amx_bf16: is_x86_feature_detected!("amx-bf16"),
         // ARM features (not available on x86)
         neon: false,
         sve: false,
+        sve2: false,
+        i8mm: false,
+        bf16: false,
+        dotprod: false,
     };
 
     // Cache information from CPUID
     let (cache_l1_kb, cache_l2_kb, cache_l3_kb) = detect_cache_info_x86(&cpuid);
 
+    // Microarchitecture from CPUID leaf 1 family/model
+    let microarch = classify_x86_microarch(&vendor, &cpuid);
+
+    // Hybrid P-core/E-core topology (Alder Lake+) via CPUID leaf 7 hybrid bit + leaf 0x1A
+    let is_hybrid = cpuid
+        .get_extended_feature_info()
+        .is_some_and(|info| info.has_hybrid());
+    let (cores_performance, cores_efficiency) = if is_hybrid {
+        detect_hybrid_topology_x86(cores_logical)
+    } else {
+        (None, None)
+    };
+
     Ok(CpuInfo {
         vendor,
         brand,
@@ -92,9 +115,100 @@ fn detect_x86(
         cache_l1_kb,
         cache_l2_kb,
         cache_l3_kb,
+        microarch,
+        cores_performance,
+        cores_efficiency,
     })
 }
 
+/// Classify each logical CPU as Performance (CPUID leaf 0x1A EAX bits[31:24] == 0x40) or
+/// Efficiency (== 0x20) by pinning the detection thread to each core in turn and re-reading
+/// CPUID leaf 0x1A there, since CPUID only reports the topology of whichever core executes
+/// the instruction. Falls back to reporting every core as a performance core if affinity
+/// pinning isn't available, so hybrid-aware callers degrade safely rather than crashing.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn detect_hybrid_topology_x86(cores_logical: usize) -> (Option<usize>, Option<usize>) {
+    let Some(core_ids) = core_affinity::get_core_ids() else {
+        return (Some(cores_logical), Some(0));
+    };
+
+    let mut performance = 0usize;
+    let mut efficiency = 0usize;
+    let mut pinned_any = false;
+
+    for core_id in core_ids {
+        if !core_affinity::set_for_current(core_id) {
+            continue;
+        }
+        pinned_any = true;
+
+        let cpuid = raw_cpuid::CpuId::new();
+        match cpuid.get_hybrid_information() {
+            Some(hybrid_info) if hybrid_info.core_type() == raw_cpuid::CoreType::Efficient => {
+                efficiency += 1;
+            }
+            // Performance core, or hybrid leaf missing/unrecognized on a hybrid-bit CPU:
+            // treat as performance so this degrades toward the non-hybrid heuristic.
+            _ => performance += 1,
+        }
+    }
+
+    if !pinned_any {
+        return (Some(cores_logical), Some(0));
+    }
+
+    (Some(performance), Some(efficiency))
+}
+
+/// Classify the x86 microarchitecture from CPUID leaf 1 EAX (vendor + effective family/model).
+///
+/// Effective family is the base family (bits[11:8]) plus the extended family
+/// (bits[27:20]) when base family is 0x0F. Effective model is the base model
+/// (bits[7:4]) OR'd with the extended model (bits[19:16] << 4) when base family
+/// is 0x06 or 0x0F — the standard Intel/AMD decoding used by LLVM's Host.cpp and
+/// Julia's CPUID tables. Unknown (vendor, family, model) triples map to `generic`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn classify_x86_microarch(vendor: &str, cpuid: &raw_cpuid::CpuId) -> Microarchitecture {
+    let Some(feature_info) = cpuid.get_feature_info() else {
+        return Microarchitecture::Generic;
+    };
+
+    let base_family = feature_info.family_id();
+    let base_model = feature_info.model_id();
+    let ext_family = feature_info.extended_family_id();
+    let ext_model = feature_info.extended_model_id();
+
+    let family = if base_family == 0x0F {
+        base_family + ext_family
+    } else {
+        base_family
+    };
+
+    let model = if base_family == 0x06 || base_family == 0x0F {
+        base_model | (ext_model << 4)
+    } else {
+        base_model
+    };
+
+    match (vendor, family, model) {
+        // Skylake-derived client/server cores (Skylake, Kaby Lake, Coffee Lake, Comet Lake)
+        ("GenuineIntel", 0x06, 0x4E | 0x5E | 0x8E | 0x9E | 0x55) => Microarchitecture::IntelSkylake,
+        // Ice Lake (client + server)
+        ("GenuineIntel", 0x06, 0x7E | 0x6A | 0x6C) => Microarchitecture::IntelIceLake,
+        // Alder Lake (first hybrid P/E-core client generation)
+        ("GenuineIntel", 0x06, 0x97 | 0x9A) => Microarchitecture::IntelAlderLake,
+        // Raptor Lake
+        ("GenuineIntel", 0x06, 0xB7 | 0xBA | 0xBF) => Microarchitecture::IntelRaptorLake,
+        // Zen 2 (Matisse/Renoir family)
+        ("AuthenticAMD", 0x17, 0x60..=0x7F) => Microarchitecture::AmdZen2,
+        // Zen 3 (Vermeer/Cezanne family)
+        ("AuthenticAMD", 0x19, 0x00..=0x4F) => Microarchitecture::AmdZen3,
+        // Zen 4 (Raphael/Genoa family)
+        ("AuthenticAMD", 0x19, 0x50..=0x7F) => Microarchitecture::AmdZen4,
+        _ => Microarchitecture::Generic,
+    }
+}
+
 /// x86 cache detection via CPUID
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 fn detect_cache_info_x86(cpuid: &raw_cpuid::CpuId) -> (Option<usize>, Option<usize>, Option<usize>) {
@@ -147,6 +261,27 @@ fn detect_arm64(
     cores_physical: usize,
     cores_logical: usize,
     frequency_mhz: Option<u64>,
+) -> Result<CpuInfo, String> {
+    // macOS exposes P/E core counts, real cache sizes and FEAT_* flags via sysctl that
+    // sysinfo doesn't surface; dispatch to a dedicated path rather than threading
+    // platform branches through every field below.
+    #[cfg(target_os = "macos")]
+    {
+        let _ = &sys;
+        return detect_arm64_macos(cores_physical, cores_logical, frequency_mhz);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    detect_arm64_generic(sys, cores_physical, cores_logical, frequency_mhz)
+}
+
+/// ARM64 detection for non-macOS platforms (Linux, Windows-on-ARM)
+#[cfg(all(target_arch = "aarch64", not(target_os = "macos")))]
+fn detect_arm64_generic(
+    sys: System,
+    cores_physical: usize,
+    cores_logical: usize,
+    frequency_mhz: Option<u64>,
 ) -> Result<CpuInfo, String> {
     // Vendor detection from CPU brand
     let brand = sys
@@ -168,15 +303,34 @@ fn detect_arm64(
 
     // ARM feature detection using runtime detection
     #[cfg(target_feature = "neon")]
-    let neon = true;
+    let mut neon = true;
     #[cfg(not(target_feature = "neon"))]
-    let neon = std::arch::is_aarch64_feature_detected!("neon");
+    let mut neon = std::arch::is_aarch64_feature_detected!("neon");
 
     // SVE detection (Scalable Vector Extension - ARM's equivalent to AVX-512)
     #[cfg(target_feature = "sve")]
-    let sve = true;
+    let mut sve = true;
     #[cfg(not(target_feature = "sve"))]
-    let sve = std::arch::is_aarch64_feature_detected!("sve");
+    let mut sve = std::arch::is_aarch64_feature_detected!("sve");
+
+    // Matrix/low-precision extensions (AI-relevant: quantized/bf16 inference)
+    let mut sve2 = std::arch::is_aarch64_feature_detected!("sve2");
+    let mut i8mm = std::arch::is_aarch64_feature_detected!("i8mm");
+    let mut bf16 = std::arch::is_aarch64_feature_detected!("bf16");
+    let mut dotprod = std::arch::is_aarch64_feature_detected!("dotprod");
+
+    // Fall back to /proc/cpuinfo's Features line when the intrinsic checks come back
+    // negative (e.g. toolchains that don't expose the is_aarch64_feature_detected gates).
+    #[cfg(target_os = "linux")]
+    if !neon || !sve || !sve2 || !i8mm || !bf16 || !dotprod {
+        let proc_features = read_linux_cpuinfo_features();
+        neon = neon || proc_features.iter().any(|f| f == "neon" || f == "asimd");
+        sve = sve || proc_features.iter().any(|f| f == "sve" || f == "sve2");
+        sve2 = sve2 || proc_features.iter().any(|f| f == "sve2");
+        i8mm = i8mm || proc_features.iter().any(|f| f == "i8mm");
+        bf16 = bf16 || proc_features.iter().any(|f| f == "bf16");
+        dotprod = dotprod || proc_features.iter().any(|f| f == "asimddp" || f == "dotprod");
+    }
 
     // ARM has different features than x86
     let features = CpuFeatures {
@@ -186,13 +340,24 @@ fn detect_arm64(
         avx2: false,
         avx512f: false,
         fma: false,
+        avx512_vnni: false,
+        avx512_bf16: false,
+        amx_tile: false,
+        amx_int8: false,
+        amx_bf16: false,
         // ARM features
         neon,
         sve,
+        sve2,
+        i8mm,
+        bf16,
+        dotprod,
     };
 
-    // Cache detection not available via standard APIs on ARM
-    // Could potentially read from /sys/devices/system/cpu on Linux
+    // Cache detection: sum per-level sizes from Linux sysfs; unavailable elsewhere.
+    #[cfg(target_os = "linux")]
+    let (cache_l1_kb, cache_l2_kb, cache_l3_kb) = read_linux_sysfs_cache_kb();
+    #[cfg(not(target_os = "linux"))]
     let (cache_l1_kb, cache_l2_kb, cache_l3_kb) = (None, None, None);
 
     Ok(CpuInfo {
@@ -206,9 +371,96 @@ fn detect_arm64(
         cache_l1_kb,
         cache_l2_kb,
         cache_l3_kb,
+        microarch: Microarchitecture::Generic,
+        cores_performance: None,
+        cores_efficiency: None,
     })
 }
 
+/// macOS Apple Silicon detection via sysctl, splitting performance cores
+/// (`hw.perflevel0.physicalcpu`) from efficiency cores (`hw.perflevel1.physicalcpu`) and
+/// reading cache/feature data sysinfo doesn't expose on macOS. Mirrors how LLVM's
+/// Host.cpp and the cpu_features library detect M-series parts.
+#[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+fn detect_arm64_macos(
+    cores_physical: usize,
+    cores_logical: usize,
+    frequency_mhz: Option<u64>,
+) -> Result<CpuInfo, String> {
+    let brand =
+        sysctl_string("machdep.cpu.brand_string").unwrap_or_else(|| "Apple Silicon".to_string());
+
+    let cores_performance = sysctl_usize("hw.perflevel0.physicalcpu");
+    let cores_efficiency = sysctl_usize("hw.perflevel1.physicalcpu");
+
+    // sysctl reports cache sizes in bytes
+    let cache_l1_kb = sysctl_usize("hw.l1dcachesize").map(|bytes| bytes / 1024);
+    let cache_l2_kb = sysctl_usize("hw.l2cachesize").map(|bytes| bytes / 1024);
+
+    let features = CpuFeatures {
+        sse42: false,
+        avx: false,
+        avx2: false,
+        avx512f: false,
+        fma: false,
+        avx512_vnni: false,
+        avx512_bf16: false,
+        amx_tile: false,
+        amx_int8: false,
+        amx_bf16: false,
+        neon: true, // every Apple Silicon part implements NEON/ASIMD
+        sve: sysctl_bool("hw.optional.arm.FEAT_SVE"),
+        sve2: sysctl_bool("hw.optional.arm.FEAT_SVE2"),
+        i8mm: sysctl_bool("hw.optional.arm.FEAT_I8MM"),
+        bf16: sysctl_bool("hw.optional.arm.FEAT_BF16"),
+        dotprod: sysctl_bool("hw.optional.arm.FEAT_DotProd"),
+    };
+
+    Ok(CpuInfo {
+        vendor: "Apple".to_string(),
+        brand,
+        architecture: std::env::consts::ARCH.to_string(),
+        cores_physical,
+        cores_logical,
+        frequency_mhz,
+        features,
+        cache_l1_kb,
+        cache_l2_kb,
+        cache_l3_kb: None, // Apple Silicon's shared SLC isn't exposed as a per-core L3
+        microarch: Microarchitecture::Generic,
+        cores_performance,
+        cores_efficiency,
+    })
+}
+
+/// Read a string-valued sysctl key via the `sysctl -n` CLI.
+#[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+fn sysctl_string(key: &str) -> Option<String> {
+    let output = std::process::Command::new("sysctl")
+        .arg("-n")
+        .arg(key)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?;
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+fn sysctl_usize(key: &str) -> Option<usize> {
+    sysctl_string(key)?.parse().ok()
+}
+
+#[cfg(all(target_arch = "aarch64", target_os = "macos"))]
+fn sysctl_bool(key: &str) -> bool {
+    sysctl_string(key)
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v != 0)
+}
+
 /// Generic fallback for other architectures
 #[cfg(not(any(
     target_arch = "x86",
@@ -237,11 +489,26 @@ fn detect_generic(
         avx2: false,
         avx512f: false,
         fma: false,
+        avx512_vnni: false,
+        avx512_bf16: false,
+        amx_tile: false,
+        amx_int8: false,
+        amx_bf16: false,
         // ARM features
         neon: false,
         sve: false,
+        sve2: false,
+        i8mm: false,
+        bf16: false,
+        dotprod: false,
     };
 
+    // Cache detection: sum per-level sizes from Linux sysfs; unavailable elsewhere.
+    #[cfg(target_os = "linux")]
+    let (cache_l1_kb, cache_l2_kb, cache_l3_kb) = read_linux_sysfs_cache_kb();
+    #[cfg(not(target_os = "linux"))]
+    let (cache_l1_kb, cache_l2_kb, cache_l3_kb) = (None, None, None);
+
     Ok(CpuInfo {
         vendor,
         brand,
@@ -250,8 +517,104 @@ fn detect_generic(
         cores_logical,
         frequency_mhz,
         features,
-        cache_l1_kb: None,
-        cache_l2_kb: None,
-        cache_l3_kb: None,
+        cache_l1_kb,
+        cache_l2_kb,
+        cache_l3_kb,
+        microarch: Microarchitecture::Generic,
+        cores_performance: None,
+        cores_efficiency: None,
     })
 }
+
+/// Parse CPU cache sizes from Linux sysfs, summing per-level sizes across the
+/// `cache/index*/` entries under `cpu0`. Used on ARM64 and other non-x86 architectures
+/// where CPUID-style cache enumeration isn't available.
+#[cfg(all(target_os = "linux", not(any(target_arch = "x86", target_arch = "x86_64"))))]
+fn read_linux_sysfs_cache_kb() -> (Option<usize>, Option<usize>, Option<usize>) {
+    use std::fs;
+
+    let (mut l1, mut l2, mut l3) = (0usize, 0usize, 0usize);
+    let (mut has_l1, mut has_l2, mut has_l3) = (false, false, false);
+
+    let base = std::path::Path::new("/sys/devices/system/cpu/cpu0/cache");
+    let Ok(entries) = fs::read_dir(base) else {
+        return (None, None, None);
+    };
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let level = fs::read_to_string(dir.join("level"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok());
+        let cache_type = fs::read_to_string(dir.join("type")).ok();
+        let size_kb = fs::read_to_string(dir.join("size"))
+            .ok()
+            .and_then(|s| parse_sysfs_cache_size_kb(s.trim()));
+
+        let (Some(level), Some(size_kb)) = (level, size_kb) else {
+            continue;
+        };
+
+        // Skip instruction-only L1 caches so `cache_l1_kb` tracks data cache, matching
+        // the x86 path above; unified/data L2 and L3 caches are summed as-is.
+        if level == 1 && cache_type.as_deref().map(str::trim) == Some("Instruction") {
+            continue;
+        }
+
+        match level {
+            1 => {
+                l1 += size_kb;
+                has_l1 = true;
+            }
+            2 => {
+                l2 += size_kb;
+                has_l2 = true;
+            }
+            3 => {
+                l3 += size_kb;
+                has_l3 = true;
+            }
+            _ => {}
+        }
+    }
+
+    (
+        has_l1.then_some(l1),
+        has_l2.then_some(l2),
+        has_l3.then_some(l3),
+    )
+}
+
+/// Parse a sysfs cache `size` value such as "48K" or "2M" into kilobytes.
+#[cfg(all(target_os = "linux", not(any(target_arch = "x86", target_arch = "x86_64"))))]
+fn parse_sysfs_cache_size_kb(raw: &str) -> Option<usize> {
+    if let Some(num) = raw.strip_suffix('K') {
+        num.parse::<usize>().ok()
+    } else if let Some(num) = raw.strip_suffix('M') {
+        num.parse::<usize>().ok().map(|mb| mb * 1024)
+    } else {
+        raw.parse::<usize>().ok()
+    }
+}
+
+/// Parse the `Features`/`Flags` line from `/proc/cpuinfo`, returning lowercase tokens.
+/// Mirrors how sysinfo's Linux backend sources CPU feature data from the same file;
+/// used to fill gaps when the intrinsic `is_aarch64_feature_detected!` checks are
+/// unavailable.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+fn read_linux_cpuinfo_features() -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string("/proc/cpuinfo") else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .find(|line| line.starts_with("Features") || line.starts_with("Flags"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, values)| values.split_whitespace().map(str::to_lowercase).collect())
+        .unwrap_or_default()
+}
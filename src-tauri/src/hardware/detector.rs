@@ -1,7 +1,7 @@
 use crate::hardware::errors::HardwareError;
 use crate::hardware::types::{
-    CpuFeatures, CpuInfo, GpuInfo, GpuVendor, HardwareInfo, MemoryInfo, NpuConfidence, NpuInfo,
-    StorageInfo,
+    CpuFeatures, CpuInfo, GpuInfo, GpuVendor, HardwareInfo, MemoryInfo, Microarchitecture,
+    NpuConfidence, NpuInfo, StorageInfo,
 };
 
 /// Helper Functions
@@ -48,9 +48,20 @@ pub async fn detect_all() -> Result<HardwareInfo, HardwareError> {
         memory: memory_info,
         storage: storage_info,
         detected_at: chrono::Utc::now().to_rfc3339(),
+        capability_score: None,
     })
 }
 
+/// Same as [`detect_all`], but also runs the synthetic CPU/memory-bandwidth
+/// micro-benchmark and populates `capability_score`. Slower than
+/// `detect_all` by the micro-benchmark's wall-clock budget, so callers that
+/// don't need the score should prefer `detect_all`.
+pub async fn detect_all_with_score() -> Result<HardwareInfo, HardwareError> {
+    let mut info = detect_all().await?;
+    info.capability_score = Some(crate::hardware::capability::measure());
+    Ok(info)
+}
+
 /// Convert hardware-query CPU info to our CpuInfo format
 fn convert_cpu_info(hw_info: &hardware_query::HardwareInfo) -> CpuInfo {
     let cpu = hw_info.cpu();
@@ -84,6 +95,8 @@ fn convert_cpu_info(hw_info: &hardware_query::HardwareInfo) -> CpuInfo {
             "CPU cache detected: L1={cache_l1_kb:?} KB, L2={cache_l2_kb:?} KB, L3={cache_l3_kb:?} KB");
     }
 
+    let (microarch, cores_performance, cores_efficiency) = detect_microarch_and_hybrid_topology();
+
     CpuInfo {
         vendor: cpu.vendor().to_string(),
         brand: cpu.model_name().to_string(),
@@ -97,12 +110,37 @@ fn convert_cpu_info(hw_info: &hardware_query::HardwareInfo) -> CpuInfo {
             avx2: cpu.has_feature("avx2"),
             avx512f: cpu.has_feature("avx512f"),
             fma: cpu.has_feature("fma"),
+            avx512_vnni: cpu.has_feature("avx512vnni"),
+            avx512_bf16: cpu.has_feature("avx512bf16"),
+            amx_tile: cpu.has_feature("amx-tile"),
+            amx_int8: cpu.has_feature("amx-int8"),
+            amx_bf16: cpu.has_feature("amx-bf16"),
             neon: cpu.has_feature("neon"),
             sve: cpu.has_feature("sve"),
+            sve2: cpu.has_feature("sve2"),
+            i8mm: cpu.has_feature("i8mm"),
+            bf16: cpu.has_feature("bf16"),
+            dotprod: cpu.has_feature("dotprod"),
         },
         cache_l1_kb,
         cache_l2_kb,
         cache_l3_kb,
+        microarch,
+        cores_performance,
+        cores_efficiency,
+    }
+}
+
+/// `hardware-query` doesn't expose microarchitecture or P-core/E-core topology, so
+/// fill those in from our own CPUID/sysctl-based `cpu` module instead. Falls back to
+/// the previous generic/unknown defaults if that detection fails for any reason.
+fn detect_microarch_and_hybrid_topology() -> (Microarchitecture, Option<usize>, Option<usize>) {
+    match crate::hardware::cpu::detect() {
+        Ok(info) => (info.microarch, info.cores_performance, info.cores_efficiency),
+        Err(e) => {
+            log::warn!("cpu module detection failed, falling back to generic values: {e}");
+            (Microarchitecture::Generic, None, None)
+        }
     }
 }
 
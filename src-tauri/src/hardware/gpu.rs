@@ -1,6 +1,18 @@
+//! wgpu-based GPU enumeration, layered with vendor-specific native probes.
+//!
+//! wgpu's portable adapter enumeration can report a GPU's name and backend
+//! but not VRAM, temperature, utilization, or CUDA compute capability - those
+//! come from a second probe pass dispatched on the detected vendor (NVML for
+//! NVIDIA, sysfs for AMD on Linux, `powermetrics` for Apple Silicon), mirroring
+//! the vendor-dispatch shape `benchmark::gpu::GpuSampler` uses for periodic
+//! telemetry. Each probe degrades to `None` per-field rather than failing
+//! `detect` outright when its backend isn't available.
+
 use crate::hardware::types::{GpuInfo, GpuVendor};
 use wgpu::{Backends, Instance, InstanceDescriptor};
 
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
 /// Detect all available GPUs
 pub async fn detect() -> Result<Vec<GpuInfo>, String> {
     // Create wgpu instance with primary backends (Vulkan, Metal, DX12)
@@ -23,16 +35,211 @@ pub async fn detect() -> Result<Vec<GpuInfo>, String> {
             GpuVendor::from_pci_id(info.vendor)
         };
 
+        let probe = probe_native(&vendor, info.vendor, info.device);
+
         gpus.push(GpuInfo {
             name: info.name.clone(),
             vendor,
             backend: format!("{:?}", info.backend),
             device_type: format!("{:?}", info.device_type),
-            vram_mb: None, // wgpu doesn't expose VRAM
-            temperature_c: None,
-            utilization_percent: None,
+            vram_mb: probe.vram_mb,
+            temperature_c: probe.temperature_c,
+            utilization_percent: probe.utilization_percent,
+            cuda_compute_capability: probe.cuda_compute_capability,
         });
     }
 
     Ok(gpus)
 }
+
+/// Fields wgpu's portable enumeration can't provide, filled in by whichever
+/// native-API probe matches `vendor`.
+#[derive(Debug, Default, Clone)]
+struct NativeGpuProbe {
+    vram_mb: Option<u64>,
+    temperature_c: Option<u32>,
+    utilization_percent: Option<u32>,
+    cuda_compute_capability: Option<String>,
+}
+
+/// Dispatch to the vendor-specific probe, matching the wgpu adapter to the
+/// native device by PCI vendor/device id where the backend supports it.
+fn probe_native(vendor: &GpuVendor, pci_vendor_id: u32, pci_device_id: u32) -> NativeGpuProbe {
+    match vendor {
+        GpuVendor::Nvidia => probe_nvidia(pci_vendor_id, pci_device_id),
+        GpuVendor::Amd => probe_amd_linux(pci_vendor_id, pci_device_id),
+        GpuVendor::Apple => probe_apple(),
+        GpuVendor::Intel | GpuVendor::Qualcomm | GpuVendor::Unknown => {
+            log::debug!("No native GPU probe for {vendor:?}, leaving native-only fields None");
+            NativeGpuProbe::default()
+        }
+    }
+}
+
+/// NVML-backed probe for NVIDIA GPUs: VRAM total, temperature, utilization,
+/// and the CUDA compute capability (major.minor) the adapter supports.
+fn probe_nvidia(pci_vendor_id: u32, pci_device_id: u32) -> NativeGpuProbe {
+    let nvml = match nvml_wrapper::Nvml::init() {
+        Ok(nvml) => nvml,
+        Err(e) => {
+            log::debug!("NVML unavailable, skipping native NVIDIA GPU probe: {e}");
+            return NativeGpuProbe::default();
+        }
+    };
+
+    let device_count = nvml.device_count().unwrap_or(0);
+    for index in 0..device_count {
+        let Ok(device) = nvml.device_by_index(index) else {
+            continue;
+        };
+        let matches = device
+            .pci_info()
+            .map(|pci| pci_ids_match(pci.pci_device_id, pci_vendor_id, pci_device_id))
+            .unwrap_or(false);
+        if !matches {
+            continue;
+        }
+
+        return NativeGpuProbe {
+            vram_mb: device.memory_info().ok().map(|m| m.total / BYTES_PER_MB),
+            temperature_c: device
+                .temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
+                .ok(),
+            utilization_percent: device.utilization_rates().ok().map(|u| u.gpu),
+            cuda_compute_capability: device
+                .cuda_compute_capability()
+                .ok()
+                .map(|cc| format!("{}.{}", cc.major, cc.minor)),
+        };
+    }
+
+    log::debug!("No NVML device matched PCI id {pci_vendor_id:04x}:{pci_device_id:04x}");
+    NativeGpuProbe::default()
+}
+
+/// NVML packs vendor+device PCI ids into a single `pci_device_id` as
+/// `(device_id << 16) | vendor_id`; wgpu's `AdapterInfo` already splits them,
+/// so compare by unpacking NVML's value instead.
+fn pci_ids_match(nvml_pci_device_id: u32, wgpu_vendor_id: u32, wgpu_device_id: u32) -> bool {
+    let vendor = nvml_pci_device_id & 0xFFFF;
+    let device = (nvml_pci_device_id >> 16) & 0xFFFF;
+    vendor == wgpu_vendor_id && device == wgpu_device_id
+}
+
+/// amdgpu sysfs probe (Linux only): matches the adapter by the PCI vendor/device
+/// hex files under `/sys/class/drm/card*/device/`, then reads VRAM/utilization/
+/// temperature from the sibling `mem_info_vram_total`/`gpu_busy_percent`/hwmon
+/// files the amdgpu kernel driver exposes. No CUDA compute capability, obviously.
+#[cfg(target_os = "linux")]
+fn probe_amd_linux(pci_vendor_id: u32, pci_device_id: u32) -> NativeGpuProbe {
+    let Some(device_dir) = find_sysfs_card(pci_vendor_id, pci_device_id) else {
+        return NativeGpuProbe::default();
+    };
+
+    NativeGpuProbe {
+        vram_mb: read_sysfs_u64(&device_dir.join("mem_info_vram_total"))
+            .map(|bytes| bytes / BYTES_PER_MB),
+        temperature_c: read_hwmon_temp(&device_dir),
+        #[allow(clippy::cast_possible_truncation)]
+        utilization_percent: read_sysfs_u64(&device_dir.join("gpu_busy_percent"))
+            .map(|v| v as u32),
+        cuda_compute_capability: None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn probe_amd_linux(_pci_vendor_id: u32, _pci_device_id: u32) -> NativeGpuProbe {
+    NativeGpuProbe::default()
+}
+
+#[cfg(target_os = "linux")]
+fn find_sysfs_card(pci_vendor_id: u32, pci_device_id: u32) -> Option<std::path::PathBuf> {
+    let entries = std::fs::read_dir("/sys/class/drm").ok()?;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with("card") || name.contains('-') {
+            continue; // skip connector nodes like "card0-DP-1"
+        }
+
+        let device_dir = entry.path().join("device");
+        let vendor = read_sysfs_hex(&device_dir.join("vendor"));
+        let device = read_sysfs_hex(&device_dir.join("device"));
+        if vendor == Some(pci_vendor_id) && device == Some(pci_device_id) {
+            return Some(device_dir);
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn read_sysfs_hex(path: &std::path::Path) -> Option<u32> {
+    let text = std::fs::read_to_string(path).ok()?;
+    u32::from_str_radix(text.trim().trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn read_sysfs_u64(path: &std::path::Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn read_hwmon_temp(device_dir: &std::path::Path) -> Option<u32> {
+    let entries = std::fs::read_dir(device_dir.join("hwmon")).ok()?;
+    for entry in entries.flatten() {
+        if let Some(millidegrees) = read_sysfs_u64(&entry.path().join("temp1_input")) {
+            #[allow(clippy::cast_possible_truncation)]
+            return Some((millidegrees / 1000) as u32);
+        }
+    }
+    None
+}
+
+/// `powermetrics`-backed probe for Apple Silicon's integrated GPU. Not reused
+/// from `benchmark::gpu::AppleGpuSampler` directly since that sampler's
+/// semantics are process-attributed for a running benchmark, while this is a
+/// one-shot device-wide detection snapshot. Apple Silicon's unified memory
+/// means there's no separate VRAM figure to report, and the `gpu_power`
+/// sampler doesn't expose temperature, so only `utilization_percent` is ever
+/// filled here.
+#[cfg(target_os = "macos")]
+fn probe_apple() -> NativeGpuProbe {
+    let output = match std::process::Command::new("powermetrics")
+        .args(["--samplers", "gpu_power", "-i", "50", "-n", "1"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            log::debug!(
+                "powermetrics exited with {} (likely lacks permissions), skipping native Apple GPU probe",
+                output.status
+            );
+            return NativeGpuProbe::default();
+        }
+        Err(e) => {
+            log::debug!("powermetrics not found on PATH, skipping native Apple GPU probe: {e}");
+            return NativeGpuProbe::default();
+        }
+    };
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let utilization_percent = text
+        .lines()
+        .find(|line| line.contains("GPU HW active residency"))
+        .and_then(|line| line.split(':').nth(1))
+        .and_then(|rest| rest.trim().trim_end_matches('%').trim().parse::<f64>().ok())
+        .map(|pct| pct.round() as u32);
+
+    NativeGpuProbe {
+        vram_mb: None,
+        temperature_c: None,
+        utilization_percent,
+        cuda_compute_capability: None,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn probe_apple() -> NativeGpuProbe {
+    NativeGpuProbe::default()
+}
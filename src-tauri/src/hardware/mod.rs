@@ -1,8 +1,11 @@
+pub mod capability;
 pub mod cpu;
 pub mod detector;
 pub mod gpu;
+pub mod monitor;
 pub mod npu;
 pub mod types;
 
-pub use detector::detect_all;
+pub use capability::CapabilityScore;
+pub use detector::{detect_all, detect_all_with_score};
 pub use types::*;
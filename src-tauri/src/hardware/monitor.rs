@@ -0,0 +1,185 @@
+//! Background hardware telemetry sampling, distinct from `detector.rs`'s
+//! one-shot full enumeration.
+//!
+//! `HardwareInfo`'s volatile fields (per-core CPU load, available memory, GPU
+//! temperature/utilization, disk free space) go stale the instant
+//! `detect_all` returns, but re-running full detection (CPUID, hardware-query's
+//! whole-device enumeration) on every tick would be far too expensive for a
+//! live dashboard. `HardwareMonitor::start` instead spawns a loop that
+//! refreshes only those fields incrementally - via `sysinfo`'s already-warm
+//! `System`/`Disks`, the same "keep it warm, call `refresh_*`" pattern
+//! `benchmark::sampling` uses - and reuses `benchmark::gpu::GpuSampler` for
+//! GPU temperature/utilization rather than re-enumerating GPUs.
+
+use crate::benchmark::gpu::GpuSampler;
+use crate::hardware::types::GpuVendor;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Disks, System};
+use tauri::Emitter;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// CPU baseline delay required by sysinfo before per-core usage is meaningful
+/// (same two-refresh-cycle requirement as `benchmark::sampling`).
+const CPU_BASELINE_DELAY: Duration = Duration::from_millis(200);
+
+/// Number of recent samples retained for `history()` queries - at a typical
+/// 1-2s sampling interval this covers several minutes of rolling-graph
+/// history without the buffer growing unbounded over a long session.
+const HISTORY_CAPACITY: usize = 600;
+
+/// Tauri event emitted on every sampling tick; the frontend listens for this
+/// to drive live/rolling hardware graphs.
+pub const TELEMETRY_EVENT: &str = "hardware://telemetry";
+
+/// One refreshed snapshot of the volatile `HardwareInfo` fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySnapshot {
+    pub timestamp_ms: u64,
+    pub cpu_load_percent: Vec<f32>,
+    pub memory_available_gb: f64,
+    pub gpu_temperature_c: Option<u32>,
+    pub gpu_utilization_percent: Option<u32>,
+    pub disk_available_gb: f64,
+}
+
+struct MonitorState {
+    running: Arc<AtomicBool>,
+    history: VecDeque<TelemetrySnapshot>,
+}
+
+/// Handle to a background telemetry sampler and its ring buffer of recent
+/// history. Cheap to clone (shares the same state), and meant to be held as
+/// managed Tauri state so `start`/`stop`/`history` commands all act on the
+/// same sampler.
+#[derive(Clone)]
+pub struct HardwareMonitor {
+    inner: Arc<Mutex<MonitorState>>,
+}
+
+impl Default for HardwareMonitor {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MonitorState {
+                running: Arc::new(AtomicBool::new(false)),
+                history: VecDeque::with_capacity(HISTORY_CAPACITY),
+            })),
+        }
+    }
+}
+
+impl HardwareMonitor {
+    /// Start sampling at `interval`, emitting each snapshot as a
+    /// [`TELEMETRY_EVENT`] via `app`. A no-op if already running - matches
+    /// `benchmark::sampling::SamplingState`'s single-sampler-per-run
+    /// assumption rather than spawning a duplicate loop.
+    pub fn start(&self, app: tauri::AppHandle, interval: Duration, gpu_vendor: GpuVendor) {
+        let mut state = self.inner.lock().expect("HardwareMonitor mutex poisoned");
+        if state.running.load(Ordering::SeqCst) {
+            log::debug!("Hardware monitor already running, ignoring start request");
+            return;
+        }
+        state.running.store(true, Ordering::SeqCst);
+
+        let running = Arc::clone(&state.running);
+        let inner = Arc::clone(&self.inner);
+        drop(state);
+
+        tokio::spawn(async move {
+            let mut sys = System::new_all();
+            sys.refresh_all();
+            sys.refresh_cpu_usage();
+            tokio::time::sleep(CPU_BASELINE_DELAY).await;
+
+            let gpu_sampler = GpuSampler::new(&gpu_vendor);
+            // GpuSampler::sample() attributes VRAM to a specific process, which
+            // this host-wide monitor doesn't report - any PID works here, it
+            // only affects a field we discard.
+            let tauri_pid = sysinfo::Pid::from_u32(std::process::id());
+            let mut disks = Disks::new_with_refreshed_list();
+
+            while running.load(Ordering::SeqCst) {
+                sys.refresh_cpu_usage();
+                sys.refresh_memory();
+                disks.refresh();
+
+                let cpu_load_percent = sys.cpus().iter().map(|c| c.cpu_usage()).collect();
+                let memory_available_gb = sys.available_memory() as f64 / BYTES_PER_GB;
+                let disk_available_gb = disks
+                    .iter()
+                    .map(|d| d.available_space())
+                    .max()
+                    .unwrap_or(0) as f64
+                    / BYTES_PER_GB;
+
+                let gpu_sample = gpu_sampler.sample(tauri_pid);
+
+                let snapshot = TelemetrySnapshot {
+                    timestamp_ms: now_ms(),
+                    cpu_load_percent,
+                    memory_available_gb,
+                    gpu_temperature_c: gpu_sample.temperature_c.map(|t| t as u32),
+                    gpu_utilization_percent: gpu_sample.utilization_percent.map(|u| u as u32),
+                    disk_available_gb,
+                };
+
+                {
+                    let mut state = inner.lock().expect("HardwareMonitor mutex poisoned");
+                    if state.history.len() >= HISTORY_CAPACITY {
+                        state.history.pop_front();
+                    }
+                    state.history.push_back(snapshot.clone());
+                }
+
+                if let Err(e) = app.emit(TELEMETRY_EVENT, &snapshot) {
+                    log::warn!("Failed to emit hardware telemetry event: {e}");
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+
+            log::info!("Hardware monitor sampling loop stopped");
+        });
+    }
+
+    /// Signal the sampling loop to stop after its current tick. Safe to call
+    /// when not running.
+    pub fn stop(&self) {
+        self.inner
+            .lock()
+            .expect("HardwareMonitor mutex poisoned")
+            .running
+            .store(false, Ordering::SeqCst);
+    }
+
+    /// Whether the sampling loop is currently active.
+    pub fn is_running(&self) -> bool {
+        self.inner
+            .lock()
+            .expect("HardwareMonitor mutex poisoned")
+            .running
+            .load(Ordering::SeqCst)
+    }
+
+    /// Snapshot of the current ring buffer, oldest first.
+    pub fn history(&self) -> Vec<TelemetrySnapshot> {
+        self.inner
+            .lock()
+            .expect("HardwareMonitor mutex poisoned")
+            .history
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
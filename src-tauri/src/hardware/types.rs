@@ -1,8 +1,5 @@
 use serde::{Deserialize, Serialize};
 
-// Re-export hardware-query's CPUFeature for direct use
-pub use hardware_query::CPUFeature;
-
 /// Complete hardware information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardwareInfo {
@@ -12,6 +9,11 @@ pub struct HardwareInfo {
     pub memory: MemoryInfo,
     pub storage: StorageInfo,
     pub detected_at: String,
+    /// Synthetic CPU/memory-bandwidth capability score, relative to a fixed
+    /// reference machine. `None` unless detection ran via
+    /// `get_or_detect_with_score` - the micro-benchmark it requires is too
+    /// slow to run on the default detection path.
+    pub capability_score: Option<crate::hardware::capability::CapabilityScore>,
 }
 
 /// CPU information
@@ -23,10 +25,68 @@ pub struct CpuInfo {
     pub cores_physical: usize,
     pub cores_logical: usize,
     pub frequency_mhz: Option<u32>,
-    pub features: Vec<CPUFeature>,
+    pub features: CpuFeatures,
     pub cache_l1_kb: Option<u32>,
     pub cache_l2_kb: Option<u32>,
     pub cache_l3_kb: Option<u32>,
+    /// Microarchitecture classified from CPUID vendor/family/model (x86 only;
+    /// `generic` on other architectures or when the family/model pair is unrecognized)
+    pub microarch: Microarchitecture,
+    /// Performance-core count on hybrid parts (Apple Silicon, Alder Lake+); `None`
+    /// when the platform has no P/E split. On a hybrid x86 part where per-core
+    /// pinning is unavailable, every logical core is counted as a performance core.
+    pub cores_performance: Option<usize>,
+    /// Efficiency-core count on hybrid parts; `None` under the same conditions as
+    /// `cores_performance`.
+    pub cores_efficiency: Option<usize>,
+}
+
+/// SIMD/matrix feature flags relevant to local-inference acceleration.
+///
+/// Covers the baseline x86/ARM vector extensions plus the newer matrix and
+/// low-precision extensions (AMX, AVX-512 VNNI/BF16, SVE2, I8MM) that LLVM/Julia's
+/// host-detection tables enumerate to drive codegen — surfaced here so the frontend
+/// can tell whether a machine can run quantized/bf16 models efficiently on CPU.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CpuFeatures {
+    // x86 baseline
+    pub sse42: bool,
+    pub avx: bool,
+    pub avx2: bool,
+    pub avx512f: bool,
+    pub fma: bool,
+    // x86 matrix/low-precision extensions
+    pub avx512_vnni: bool,
+    pub avx512_bf16: bool,
+    pub amx_tile: bool,
+    pub amx_int8: bool,
+    pub amx_bf16: bool,
+    // ARM baseline
+    pub neon: bool,
+    pub sve: bool,
+    // ARM matrix/low-precision extensions
+    pub sve2: bool,
+    pub i8mm: bool,
+    pub bf16: bool,
+    pub dotprod: bool,
+}
+
+/// x86 microarchitecture, classified from the CPUID leaf 1 (vendor, family, model) triple
+///
+/// Modeled after the microarchitecture enumeration used by LLVM/Julia host detection,
+/// trimmed to the generations relevant for local-inference capability reasoning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Microarchitecture {
+    IntelSkylake,
+    IntelIceLake,
+    IntelAlderLake,
+    IntelRaptorLake,
+    AmdZen2,
+    AmdZen3,
+    AmdZen4,
+    /// Unknown or non-x86 silicon
+    Generic,
 }
 
 /// GPU information
@@ -53,6 +113,39 @@ pub enum GpuVendor {
     Unknown,
 }
 
+impl GpuVendor {
+    /// Classify by PCI vendor id. Apple Silicon reports `0` here (there's no
+    /// PCI bus), so callers should fall back to [`Self::from_name`] in that case.
+    pub fn from_pci_id(vendor_id: u32) -> Self {
+        match vendor_id {
+            0x10DE => GpuVendor::Nvidia,
+            0x1002 => GpuVendor::Amd,
+            0x8086 => GpuVendor::Intel,
+            0x106B => GpuVendor::Apple,
+            0x5143 => GpuVendor::Qualcomm,
+            _ => GpuVendor::Unknown,
+        }
+    }
+
+    /// Classify by adapter name substring, for the vendor-id-`0` case above.
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("nvidia") {
+            GpuVendor::Nvidia
+        } else if name.contains("amd") || name.contains("radeon") || name.contains("ati") {
+            GpuVendor::Amd
+        } else if name.contains("intel") {
+            GpuVendor::Intel
+        } else if name.contains("apple") {
+            GpuVendor::Apple
+        } else if name.contains("qualcomm") || name.contains("adreno") {
+            GpuVendor::Qualcomm
+        } else {
+            GpuVendor::Unknown
+        }
+    }
+}
+
 
 /// NPU information
 #[derive(Debug, Clone, Serialize, Deserialize)]
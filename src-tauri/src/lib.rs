@@ -4,7 +4,11 @@ mod hardware;
 mod security;
 use commands::benchmark::{get_benchmarks_directory, open_benchmarks_folder, run_benchmark};
 use commands::default::{read, save_code, write};
-use commands::hardware::{detect_hardware, get_cached_hardware, HardwareCache};
+use commands::hardware::{
+    detect_hardware, detect_hardware_with_score, get_cached_hardware, hardware_monitor_history,
+    hardware_monitor_start, hardware_monitor_stop, HardwareCache,
+};
+use hardware::monitor::HardwareMonitor;
 use commands::ollama::{
     cancel_generation, check_ollama, generate_stream, get_ollama_models, HttpClient,
     OllamaConfig, StreamCancellation,
@@ -34,6 +38,7 @@ pub fn run() {
         .manage(HttpClient::default())
         .manage(OllamaConfig::default())
         .manage(HardwareCache::default())
+        .manage(HardwareMonitor::default())
         .invoke_handler(tauri::generate_handler![
             read,
             write,
@@ -46,7 +51,11 @@ pub fn run() {
             get_benchmarks_directory,
             open_benchmarks_folder,
             detect_hardware,
-            get_cached_hardware
+            detect_hardware_with_score,
+            get_cached_hardware,
+            hardware_monitor_start,
+            hardware_monitor_stop,
+            hardware_monitor_history
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
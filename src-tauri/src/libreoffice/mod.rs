@@ -12,17 +12,27 @@
 /// ## Modules
 ///
 /// - `types`: JSON-RPC and MCP protocol types
+/// - `transport`: async transport draining the server's stdout and demultiplexing
+///   responses/notifications
 /// - `process_manager`: Python process lifecycle management
 /// - `mcp_client`: Core MCP client with JSON-RPC communication
+/// - `orchestration`: multi-step tool-calling loops with result reuse across steps
+/// - `script`: optional Lua macro engine for chaining tool calls (behind the `scripting` feature)
 /// - `commands`: Tauri command handlers (coming soon)
 
 pub mod mcp_client;
+pub mod orchestration;
 pub mod process_manager;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod transport;
 pub mod types;
 
 // Re-export commonly used types
 #[allow(unused_imports)]
-pub use mcp_client::MCPClient;
+pub use mcp_client::{MCPClient, McpEvent};
+#[allow(unused_imports)]
+pub use orchestration::{ToolLoopResolver, ToolLoopStep, ToolLoopStepOutcome};
 #[allow(unused_imports)]
 pub use process_manager::ProcessManager;
 #[allow(unused_imports)]
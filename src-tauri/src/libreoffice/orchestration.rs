@@ -0,0 +1,101 @@
+//! Multi-step tool-calling orchestration for [`MCPClient`], with result reuse
+//! across steps.
+//!
+//! `MCPClient::call_tool` only models a single round-trip, so driving a sequence
+//! of dependent LibreOffice operations (e.g. `create_blank_document` producing a
+//! document handle that `insert_text` then targets) would otherwise force the
+//! caller to hand-thread results between calls. [`MCPClient::run_tool_loop`]
+//! takes a static plan of [`ToolLoopStep`]s and a resolver closure that folds the
+//! keyed store of prior steps' `structured_content` into each step's arguments
+//! immediately before it executes.
+
+use crate::libreoffice::{LibreOfficeError, MCPClient, ToolCallParams, ToolCallResult};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// One step of a [`MCPClient::run_tool_loop`] plan: the tool call to make, and
+/// the key its `structured_content` is stored under for later steps' resolvers
+/// to look up.
+#[derive(Debug, Clone)]
+pub struct ToolLoopStep {
+    pub key: String,
+    pub params: ToolCallParams,
+}
+
+impl ToolLoopStep {
+    pub fn new(key: impl Into<String>, params: ToolCallParams) -> Self {
+        Self {
+            key: key.into(),
+            params,
+        }
+    }
+}
+
+/// Resolver invoked immediately before a step executes: given the step's static
+/// `ToolCallParams` and the keyed store of prior steps' `structured_content`,
+/// returns the arguments to actually send. A resolver that ignores the store
+/// and returns `params.arguments.clone()` unchanged is a valid no-op plan.
+pub type ToolLoopResolver = dyn Fn(&ToolCallParams, &HashMap<String, Value>) -> Value + Send + Sync;
+
+/// One step's outcome in a [`MCPClient::run_tool_loop`] transcript: the
+/// arguments actually sent (post-resolution) and the parsed tool result.
+#[derive(Debug, Clone)]
+pub struct ToolLoopStepOutcome {
+    pub params: ToolCallParams,
+    pub result: ToolCallResult,
+}
+
+impl MCPClient {
+    /// Run an ordered plan of tool calls, threading each step's
+    /// `structured_content` into a keyed store so `resolver` can fold prior
+    /// results into later steps' arguments.
+    ///
+    /// Stops after at most `max_steps` calls regardless of how many `steps`
+    /// remain, bounding a misbehaving or unexpectedly long plan, and
+    /// short-circuits as soon as a result comes back with `is_error ==
+    /// Some(true)` - the transcript up to and including that failing step is
+    /// still returned rather than discarded.
+    pub async fn run_tool_loop(
+        &self,
+        steps: Vec<ToolLoopStep>,
+        resolver: &ToolLoopResolver,
+        max_steps: usize,
+    ) -> Result<Vec<ToolLoopStepOutcome>, LibreOfficeError> {
+        let mut store: HashMap<String, Value> = HashMap::new();
+        let mut transcript = Vec::new();
+
+        for step in steps.into_iter().take(max_steps) {
+            let arguments = resolver(&step.params, &store);
+            let resolved_params = ToolCallParams {
+                name: step.params.name.clone(),
+                arguments,
+            };
+
+            let raw_result = self
+                .call_tool(resolved_params.name.clone(), resolved_params.arguments.clone())
+                .await?;
+            let result: ToolCallResult = serde_json::from_value(raw_result).map_err(|e| {
+                LibreOfficeError::InvalidResponse(format!(
+                    "Failed to parse tool call result for step '{}': {}",
+                    step.key, e
+                ))
+            })?;
+
+            if let Some(structured) = result.structured_content.clone() {
+                store.insert(step.key.clone(), structured);
+            }
+
+            let is_error = result.is_error == Some(true);
+            transcript.push(ToolLoopStepOutcome {
+                params: resolved_params,
+                result,
+            });
+
+            if is_error {
+                break;
+            }
+        }
+
+        Ok(transcript)
+    }
+}
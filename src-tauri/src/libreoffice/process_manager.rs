@@ -1,28 +1,111 @@
 use crate::libreoffice::types::LibreOfficeError;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
+use tokio::sync::{Mutex, Notify};
+use tokio::time::{Duration, Instant};
+
+/// Spawn-time configuration for `ProcessManager`, mirroring the env/dir/args surface of
+/// std's `Command` builder. Lets callers point at a venv/conda interpreter or inject
+/// `PYTHONPATH`/`UNO_PATH`/a LibreOffice profile dir without code changes.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnConfig {
+    python_executable: Option<PathBuf>,
+    extra_args: Vec<String>,
+    env: Vec<(String, String)>,
+    current_dir: Option<PathBuf>,
+}
+
+impl SpawnConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override Python discovery with an explicit interpreter path.
+    pub fn python_executable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.python_executable = Some(path.into());
+        self
+    }
+
+    /// Append a single CLI argument after the server script path.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.extra_args.push(arg.into());
+        self
+    }
+
+    /// Append multiple CLI arguments after the server script path.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_args.extend(args.into_iter().map(Into::into));
+        self
+    }
 
-/// Manages the Python MCP server process lifecycle
+    /// Set an environment variable in the spawned process.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the spawned process's working directory.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+}
+
+/// Number of recent stderr lines retained for crash diagnostics.
+const LOG_TAIL_CAPACITY: usize = 100;
+
+/// Manages the Python MCP server process's lifecycle (kill/wait/liveness) and
+/// stderr log tail.
+///
+/// Deliberately does *not* own stdin/stdout: `spawn`/`spawn_with_config` hand
+/// those back as independent values so a caller (namely
+/// [`super::transport::Transport`]) can lock/own them separately from this
+/// struct and from each other. Bundling all three behind one mutex used to
+/// mean a pending read on stdout (which blocks indefinitely whenever the
+/// server has nothing to say) serialized every write and every cancellation
+/// notification behind it.
 pub struct ProcessManager {
     child: Child,
-    pub stdin: ChildStdin,
-    pub stdout: BufReader<ChildStdout>,
-    pub stderr: BufReader<ChildStderr>,
+    /// Last `LOG_TAIL_CAPACITY` lines written to the child's stderr, drained by a
+    /// background task spawned alongside the process. Attached to `ProcessCrashed`
+    /// errors so failures aren't opaque.
+    log_tail: Arc<Mutex<VecDeque<String>>>,
+    log_pump: tokio::task::JoinHandle<()>,
 }
 
 impl ProcessManager {
-    /// Spawn the Python MCP server process
+    /// Spawn the Python MCP server process with default configuration (auto-discovered
+    /// interpreter, no extra args/env, inherited working directory).
     ///
     /// This will:
     /// 1. Find the Python executable
     /// 2. Find the MCP server script
     /// 3. Spawn the process with stdio pipes
     /// 4. Return handles for stdin/stdout/stderr
-    pub async fn spawn() -> Result<Self, LibreOfficeError> {
-        // Find Python executable
-        let python_exe = find_python_executable()?;
+    pub async fn spawn() -> Result<(Self, ChildStdin, BufReader<ChildStdout>), LibreOfficeError> {
+        Self::spawn_with_config(SpawnConfig::default()).await
+    }
+
+    /// Spawn the Python MCP server process using a `SpawnConfig`, e.g. to point at a
+    /// venv interpreter or inject `PYTHONPATH`/`UNO_PATH`. Returns the process manager
+    /// (for kill/wait/liveness) alongside independent stdin/stdout handles.
+    pub async fn spawn_with_config(
+        config: SpawnConfig,
+    ) -> Result<(Self, ChildStdin, BufReader<ChildStdout>), LibreOfficeError> {
+        // Find Python executable (explicit override takes priority)
+        let python_exe = match config.python_executable.clone() {
+            Some(path) => path,
+            None => find_python_executable()?,
+        };
         log::info!("Found Python executable: {:?}", python_exe);
 
         // Find MCP server script
@@ -30,19 +113,26 @@ impl ProcessManager {
         log::info!("Found MCP server script: {:?}", server_script);
 
         // Spawn the process
-        let mut child = Command::new(&python_exe)
+        let mut command = Command::new(&python_exe);
+        command
             .arg(&server_script)
+            .args(&config.extra_args)
+            .envs(config.env.iter().cloned())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .kill_on_drop(true) // Automatically kill when dropped
-            .spawn()
-            .map_err(|e| {
-                LibreOfficeError::ProcessSpawnFailed(format!(
-                    "Failed to spawn Python process: {}",
-                    e
-                ))
-            })?;
+            .kill_on_drop(true); // Automatically kill when dropped
+
+        if let Some(dir) = &config.current_dir {
+            command.current_dir(dir);
+        }
+
+        let mut child = command.spawn().map_err(|e| {
+            LibreOfficeError::ProcessSpawnFailed(format!(
+                "Failed to spawn Python process: {}",
+                e
+            ))
+        })?;
 
         // Take ownership of stdin/stdout/stderr
         let stdin = child.stdin.take().ok_or_else(|| {
@@ -59,12 +149,34 @@ impl ProcessManager {
 
         log::info!("MCP server process spawned successfully (PID: {:?})", child.id());
 
-        Ok(Self {
-            child,
+        let log_tail = Arc::new(Mutex::new(VecDeque::with_capacity(LOG_TAIL_CAPACITY)));
+        let log_pump = spawn_stderr_pump(stderr, log_tail.clone());
+
+        Ok((
+            Self {
+                child,
+                log_tail,
+                log_pump,
+            },
             stdin,
-            stdout: BufReader::new(stdout),
-            stderr: BufReader::new(stderr),
-        })
+            BufReader::new(stdout),
+        ))
+    }
+
+    /// The last `LOG_TAIL_CAPACITY` lines the child wrote to stderr, oldest first. Useful
+    /// for surfacing recent MCP server output in a UI, independent of crash handling.
+    pub async fn recent_log_lines(&self) -> Vec<String> {
+        self.log_tail.lock().await.iter().cloned().collect()
+    }
+
+    /// Render the stderr tail as a single string for embedding in an error message.
+    async fn log_tail_context(&self) -> String {
+        let lines = self.recent_log_lines().await;
+        if lines.is_empty() {
+            "(no stderr output captured)".to_string()
+        } else {
+            format!("recent stderr:\n{}", lines.join("\n"))
+        }
     }
 
     /// Check if the process is still running
@@ -84,123 +196,458 @@ impl ProcessManager {
     /// Kill the process
     pub async fn kill(&mut self) -> Result<(), LibreOfficeError> {
         log::info!("Killing MCP server process (PID: {:?})", self.child.id());
-        self.child.kill().await.map_err(|e| {
-            LibreOfficeError::ProcessCrashed(format!("Failed to kill process: {}", e))
-        })?;
+        if let Err(e) = self.child.kill().await {
+            let tail = self.log_tail_context().await;
+            return Err(LibreOfficeError::ProcessCrashed(format!(
+                "Failed to kill process: {e}; {tail}"
+            )));
+        }
         Ok(())
     }
 
     /// Wait for the process to exit and get its status
     pub async fn wait(&mut self) -> Result<std::process::ExitStatus, LibreOfficeError> {
-        self.child.await.map_err(|e| {
-            LibreOfficeError::ProcessCrashed(format!("Error waiting for process: {}", e))
-        })
+        match self.child.wait().await {
+            Ok(status) => Ok(status),
+            Err(e) => {
+                let tail = self.log_tail_context().await;
+                Err(LibreOfficeError::ProcessCrashed(format!(
+                    "Error waiting for process: {e}; {tail}"
+                )))
+            }
+        }
     }
+
+    /// Ask the child to terminate politely, then escalate to a hard kill if it doesn't
+    /// exit within `timeout`. On Unix this sends `SIGTERM` so the Python MCP server can
+    /// flush state and release LibreOffice UNO connections; Windows has no equivalent
+    /// signal, so it waits out the timeout and falls back to `kill()` directly.
+    pub async fn shutdown(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<std::process::ExitStatus, LibreOfficeError> {
+        self.request_graceful_exit();
+
+        match tokio::time::timeout(timeout, self.child.wait()).await {
+            Ok(Ok(status)) => {
+                log::info!("MCP server exited gracefully: {status:?}");
+                Ok(status)
+            }
+            Ok(Err(e)) => {
+                let tail = self.log_tail_context().await;
+                Err(LibreOfficeError::ProcessCrashed(format!(
+                    "Error waiting for process: {e}; {tail}"
+                )))
+            }
+            Err(_elapsed) => {
+                log::warn!(
+                    "MCP server did not exit within {timeout:?} of graceful shutdown request; killing"
+                );
+                self.kill().await?;
+                self.wait().await
+            }
+        }
+    }
+
+    /// Send SIGTERM to the child (Unix only — Windows has no equivalent signal and relies
+    /// on the `shutdown` timeout/kill fallback).
+    #[cfg(unix)]
+    fn request_graceful_exit(&self) {
+        if let Some(pid) = self.child.id() {
+            // SAFETY: `kill` with SIGTERM only requests termination of our own child
+            // process; it performs no memory access and cannot be unsafe to call.
+            let result = unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+            if result != 0 {
+                log::warn!(
+                    "Failed to send SIGTERM to MCP server process (PID: {pid}): {}",
+                    std::io::Error::last_os_error()
+                );
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn request_graceful_exit(&self) {}
 }
 
 impl Drop for ProcessManager {
     fn drop(&mut self) {
         log::info!("ProcessManager dropped, process will be killed automatically");
+        self.log_pump.abort();
     }
 }
 
-/// Find the Python executable on the system
-fn find_python_executable() -> Result<PathBuf, LibreOfficeError> {
-    #[cfg(target_os = "macos")]
-    {
-        // macOS: Try common Python paths
-        let candidates = vec![
-            "/usr/bin/python3",
-            "/usr/local/bin/python3",
-            "/opt/homebrew/bin/python3", // Apple Silicon Homebrew
-            "/usr/bin/python",
-        ];
-
-        for path in candidates {
-            if Path::new(path).exists() {
-                return Ok(PathBuf::from(path));
+/// Spawn a background task that drains the child's stderr line by line, forwarding each
+/// line to the log at `warn` level (with a `[mcp-server]` prefix) while retaining the
+/// last `LOG_TAIL_CAPACITY` lines in `tail` for crash diagnostics. Draining stderr keeps
+/// the OS pipe buffer from filling and deadlocking the child if it logs heavily.
+fn spawn_stderr_pump(
+    stderr: ChildStderr,
+    tail: Arc<Mutex<VecDeque<String>>>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    log::warn!("[mcp-server] {line}");
+                    let mut tail = tail.lock().await;
+                    if tail.len() == LOG_TAIL_CAPACITY {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                }
+                Ok(None) => break, // EOF: child closed stderr (exited or about to)
+                Err(e) => {
+                    log::debug!("Error reading MCP server stderr: {e}");
+                    break;
+                }
             }
         }
+    })
+}
+
+/// Caps how many restarts `SupervisedProcess` allows within a rolling time window
+/// before tripping its circuit breaker and giving up.
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    pub max_restarts: u32,
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            window: Duration::from_secs(60),
+        }
     }
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        // Windows: First try `where` command to find python
-        if let Ok(output) = std::process::Command::new("where").arg("python").output() {
-            if output.status.success() {
-                if let Ok(stdout) = String::from_utf8(output.stdout) {
-                    if let Some(first_line) = stdout.lines().next() {
-                        let path = PathBuf::from(first_line.trim());
-                        if path.exists() {
-                            return Ok(path);
-                        }
-                    }
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+const RESTART_STABILITY_WINDOW: Duration = Duration::from_secs(10);
+const LIVENESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Supervises a `ProcessManager`, automatically respawning the MCP server process if it
+/// exits unexpectedly. Restarts back off exponentially with jitter (capped at
+/// `MAX_RESTART_BACKOFF`), and the backoff resets once a respawned child survives past
+/// `RESTART_STABILITY_WINDOW`. A `RestartPolicy` circuit-breaks repeated rapid crashes
+/// instead of restarting forever, surfacing `LibreOfficeError::ProcessCrashed` via
+/// `failure()`.
+///
+/// Because a restart replaces the `ProcessManager` (and therefore its stdin/stdout/stderr
+/// handles) entirely, callers should go through `process_handle()`/`stdin_handle()`/
+/// `stdout_handle()` on each use rather than caching a guard, so they transparently
+/// observe the fresh pipes after a restart.
+///
+/// `stdin`/`stdout` are locked independently of `inner` (and of each other) - a write
+/// or a control-plane check (`is_running`, etc.) never blocks behind a pending read, the
+/// way bundling all three behind one mutex would.
+pub struct SupervisedProcess {
+    inner: Arc<Mutex<ProcessManager>>,
+    stdin: Arc<Mutex<ChildStdin>>,
+    stdout: Arc<Mutex<BufReader<ChildStdout>>>,
+    failure: Arc<Mutex<Option<String>>>,
+    shutdown: Arc<Notify>,
+    watchdog: tokio::task::JoinHandle<()>,
+}
+
+impl SupervisedProcess {
+    /// Spawn the MCP server process under supervision.
+    pub async fn spawn(restart_policy: RestartPolicy) -> Result<Self, LibreOfficeError> {
+        let (manager, child_stdin, child_stdout) = ProcessManager::spawn().await?;
+        let inner = Arc::new(Mutex::new(manager));
+        let stdin = Arc::new(Mutex::new(child_stdin));
+        let stdout = Arc::new(Mutex::new(child_stdout));
+        let failure = Arc::new(Mutex::new(None));
+        let shutdown = Arc::new(Notify::new());
+
+        let watchdog = tokio::spawn(Self::watchdog_loop(
+            inner.clone(),
+            stdin.clone(),
+            stdout.clone(),
+            restart_policy,
+            failure.clone(),
+            shutdown.clone(),
+        ));
+
+        Ok(Self {
+            inner,
+            stdin,
+            stdout,
+            failure,
+            shutdown,
+            watchdog,
+        })
+    }
+
+    /// Shared handle to the current `ProcessManager`, for kill/wait/liveness. Re-lock on
+    /// each use — the guarded value is swapped out whenever the watchdog restarts the
+    /// process.
+    pub fn process_handle(&self) -> Arc<Mutex<ProcessManager>> {
+        self.inner.clone()
+    }
+
+    /// Shared handle to the current stdin pipe, independent of `process_handle()`/
+    /// `stdout_handle()`'s locks.
+    pub fn stdin_handle(&self) -> Arc<Mutex<ChildStdin>> {
+        self.stdin.clone()
+    }
+
+    /// Shared handle to the current stdout pipe, independent of `process_handle()`/
+    /// `stdin_handle()`'s locks.
+    pub fn stdout_handle(&self) -> Arc<Mutex<BufReader<ChildStdout>>> {
+        self.stdout.clone()
+    }
+
+    /// Whether the supervised process is currently alive.
+    pub async fn is_running(&self) -> bool {
+        self.inner.lock().await.is_running()
+    }
+
+    /// The error recorded once the restart circuit breaker trips. `None` while
+    /// supervision is still actively restarting (or the process is healthy).
+    pub async fn failure(&self) -> Option<LibreOfficeError> {
+        self.failure
+            .lock()
+            .await
+            .clone()
+            .map(LibreOfficeError::ProcessCrashed)
+    }
+
+    /// Stop the watchdog and let the underlying process be killed via `Drop`.
+    pub async fn shutdown(self) {
+        self.shutdown.notify_one();
+        let _ = self.watchdog.await;
+    }
+
+    async fn watchdog_loop(
+        inner: Arc<Mutex<ProcessManager>>,
+        stdin: Arc<Mutex<ChildStdin>>,
+        stdout: Arc<Mutex<BufReader<ChildStdout>>>,
+        policy: RestartPolicy,
+        failure: Arc<Mutex<Option<String>>>,
+        shutdown: Arc<Notify>,
+    ) {
+        let mut backoff = INITIAL_RESTART_BACKOFF;
+        let mut last_restart_at: Option<Instant> = None;
+        let mut restart_times: Vec<Instant> = Vec::new();
+
+        loop {
+            tokio::select! {
+                _ = shutdown.notified() => return,
+                _ = tokio::time::sleep(LIVENESS_POLL_INTERVAL) => {}
+            }
+
+            let alive = inner.lock().await.is_running();
+            if alive {
+                continue;
+            }
+
+            // A respawned child that survived past the stability window resets the
+            // backoff, so a rare crash long after a restart doesn't inherit the delay
+            // from an earlier crash loop.
+            if let Some(last) = last_restart_at {
+                if last.elapsed() >= RESTART_STABILITY_WINDOW {
+                    backoff = INITIAL_RESTART_BACKOFF;
                 }
             }
-        }
 
-        // Try python3 as well
-        if let Ok(output) = std::process::Command::new("where").arg("python3").output() {
-            if output.status.success() {
-                if let Ok(stdout) = String::from_utf8(output.stdout) {
-                    if let Some(first_line) = stdout.lines().next() {
-                        let path = PathBuf::from(first_line.trim());
-                        if path.exists() {
-                            return Ok(path);
-                        }
-                    }
+            let now = Instant::now();
+            restart_times.retain(|t| now.duration_since(*t) <= policy.window);
+            if restart_times.len() as u32 >= policy.max_restarts {
+                let message = format!(
+                    "MCP server crashed {} times within {:?}; restart circuit breaker tripped",
+                    restart_times.len(),
+                    policy.window
+                );
+                log::error!("{message}");
+                *failure.lock().await = Some(message);
+                return;
+            }
+
+            let delay = backoff + Duration::from_millis(jitter_ms(backoff));
+            log::warn!("MCP server exited unexpectedly; restarting in {delay:?}");
+            tokio::select! {
+                _ = shutdown.notified() => return,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            match ProcessManager::spawn().await {
+                Ok((fresh_manager, fresh_stdin, fresh_stdout)) => {
+                    *inner.lock().await = fresh_manager;
+                    *stdin.lock().await = fresh_stdin;
+                    *stdout.lock().await = fresh_stdout;
+                    last_restart_at = Some(Instant::now());
+                    restart_times.push(Instant::now());
+                    backoff = (backoff * 2).min(MAX_RESTART_BACKOFF);
+                }
+                Err(e) => {
+                    log::error!("Failed to restart MCP server process: {e}");
                 }
             }
         }
+    }
+}
 
-        // Try common Python installation paths
-        let candidates = vec![
-            r"C:\Python39\python.exe",
-            r"C:\Python310\python.exe",
-            r"C:\Python311\python.exe",
-            r"C:\Python312\python.exe",
-        ];
+/// Small random jitter (0-25% of `backoff`) so repeated restarts don't retry in lockstep.
+fn jitter_ms(backoff: Duration) -> u64 {
+    let max_jitter = (backoff.as_millis() as u64 / 4).max(1);
+    rand::thread_rng().gen_range(0..max_jitter)
+}
+
+/// Minimum supported Python version (major, minor). Interpreters older than this are
+/// rejected with `PythonVersionTooOld` rather than being handed to the MCP server, where
+/// they'd fail more cryptically on import.
+const MIN_PYTHON_VERSION: (u32, u32) = (3, 9);
+
+/// Find a Python interpreter on the system meeting `MIN_PYTHON_VERSION`.
+///
+/// Consults, in priority order: the `SMOLPC_PYTHON`/`PYTHON` env var overrides, an
+/// active `VIRTUAL_ENV`/`CONDA_PREFIX` environment, a real PATH search for
+/// `python3`/`python` across every PATH entry (catching pyenv/asdf shims that a fixed
+/// path list misses), and finally the historical fixed install-location fallbacks.
+fn find_python_executable() -> Result<PathBuf, LibreOfficeError> {
+    let mut version_too_old = None;
 
-        for path in candidates {
-            if Path::new(path).exists() {
-                return Ok(PathBuf::from(path));
+    for candidate in python_candidates() {
+        if !candidate.exists() {
+            continue;
+        }
+
+        match python_version(&candidate) {
+            Some(version) if version >= MIN_PYTHON_VERSION => return Ok(candidate),
+            Some((major, minor)) => {
+                version_too_old.get_or_insert(format!("{major}.{minor} ({})", candidate.display()));
             }
+            None => {} // `--version` failed or was unparseable; skip rather than guess
         }
+    }
 
-        // Try user-specific paths (expand %USERPROFILE%)
-        if let Ok(user_profile) = std::env::var("USERPROFILE") {
-            let user_candidates = vec![
-                format!("{}\\AppData\\Local\\Programs\\Python\\Python39\\python.exe", user_profile),
-                format!("{}\\AppData\\Local\\Programs\\Python\\Python310\\python.exe", user_profile),
-                format!("{}\\AppData\\Local\\Programs\\Python\\Python311\\python.exe", user_profile),
-                format!("{}\\AppData\\Local\\Programs\\Python\\Python312\\python.exe", user_profile),
-            ];
-
-            for path in user_candidates {
-                if Path::new(&path).exists() {
-                    return Ok(PathBuf::from(path));
-                }
+    match version_too_old {
+        Some(version) => Err(LibreOfficeError::PythonVersionTooOld(version)),
+        None => Err(LibreOfficeError::PythonNotFound),
+    }
+}
+
+/// Candidate interpreter paths to probe, in priority order. Earlier candidates shadow
+/// later ones only in the sense of being tried first — `find_python_executable` still
+/// falls through to the next candidate if an earlier one is missing or too old.
+fn python_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // 1. Explicit overrides
+    for var in ["SMOLPC_PYTHON", "PYTHON"] {
+        if let Ok(path) = std::env::var(var) {
+            if !path.is_empty() {
+                candidates.push(PathBuf::from(path));
             }
         }
     }
 
-    #[cfg(target_os = "linux")]
+    // 2. Active virtualenv / conda environment
+    for var in ["VIRTUAL_ENV", "CONDA_PREFIX"] {
+        if let Ok(prefix) = std::env::var(var) {
+            let prefix = PathBuf::from(prefix);
+            #[cfg(target_os = "windows")]
+            candidates.push(prefix.join("Scripts").join("python.exe"));
+            #[cfg(not(target_os = "windows"))]
+            candidates.push(prefix.join("bin").join("python3"));
+        }
+    }
+
+    // 3. Real PATH search, not just a fixed list of absolute paths
+    if let Ok(path_var) = std::env::var("PATH") {
+        let exe_names: &[&str] = if cfg!(target_os = "windows") {
+            &["python.exe", "python3.exe"]
+        } else {
+            &["python3", "python"]
+        };
+
+        for dir in std::env::split_paths(&path_var) {
+            for name in exe_names {
+                candidates.push(dir.join(name));
+            }
+        }
+    }
+
+    // 4. Historical fixed-path fallbacks, kept for setups with an unusual/empty PATH
+    candidates.extend(legacy_fallback_candidates());
+
+    candidates
+}
+
+/// The original fixed-path candidate list, kept as a last-resort fallback.
+fn legacy_fallback_candidates() -> Vec<PathBuf> {
+    #[cfg(target_os = "macos")]
     {
-        // Linux: Try common Python paths
-        let candidates = vec![
+        vec![
             "/usr/bin/python3",
             "/usr/local/bin/python3",
+            "/opt/homebrew/bin/python3", // Apple Silicon Homebrew
             "/usr/bin/python",
-        ];
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+    }
 
-        for path in candidates {
-            if Path::new(path).exists() {
-                return Ok(PathBuf::from(path));
+    #[cfg(target_os = "windows")]
+    {
+        let mut candidates: Vec<PathBuf> = vec![
+            r"C:\Python39\python.exe",
+            r"C:\Python310\python.exe",
+            r"C:\Python311\python.exe",
+            r"C:\Python312\python.exe",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        if let Ok(user_profile) = std::env::var("USERPROFILE") {
+            for version in ["Python39", "Python310", "Python311", "Python312"] {
+                candidates.push(PathBuf::from(format!(
+                    "{user_profile}\\AppData\\Local\\Programs\\Python\\{version}\\python.exe"
+                )));
             }
         }
+
+        candidates
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        vec!["/usr/bin/python3", "/usr/local/bin/python3", "/usr/bin/python"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect()
     }
 
-    Err(LibreOfficeError::PythonNotFound)
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Vec::new()
+    }
+}
+
+/// Run `<path> --version` and parse the `Major.Minor` pair. Python 2 prints the version
+/// to stderr rather than stdout, so both streams are checked.
+fn python_version(path: &Path) -> Option<(u32, u32)> {
+    let output = std::process::Command::new(path)
+        .arg("--version")
+        .output()
+        .ok()?;
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let version_str = combined.trim().strip_prefix("Python ")?;
+    let mut parts = version_str.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
 }
 
 /// Find the MCP server script (main.py)
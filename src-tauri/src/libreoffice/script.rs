@@ -0,0 +1,188 @@
+//! Lua scripting engine for chaining MCP tool calls into macros.
+//!
+//! The convenience commands in `commands::libreoffice` each make a single
+//! tool call, so a multi-step document workflow (create, then add text, then
+//! save) forces the frontend into several round-trips. This module embeds a
+//! Lua interpreter (`mlua`) and binds the connected `MCPClient` to scripts as
+//! an `office` object, so a macro like:
+//!
+//! ```lua
+//! office:call("create_blank_document", { filename = "report.odt" })
+//! office:call("add_text", { text = "Hello" })
+//! office:call("save_document", {})
+//! ```
+//!
+//! runs as a single `libreoffice_run_script` command.
+//!
+//! Gated behind the `scripting` cargo feature - embedding a Lua interpreter is
+//! a meaningfully heavier dependency than anything else this module needs for
+//! its core connect/list/call flow.
+
+use crate::libreoffice::{LibreOfficeError, MCPClient};
+use mlua::{Lua, LuaOptions, LuaSerdeExt, StdLib, Table, Value as LuaValue};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// Outcome of one `office:call(...)` step, recorded regardless of whether the
+/// tool call itself succeeded - a failing step's context (which tool, which
+/// arguments, why it failed) is still worth returning to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptStepResult {
+    pub tool_name: String,
+    pub success: bool,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+/// Full transcript of a script run: every step attempted, in order, plus
+/// whether the Lua script itself ran to completion (as opposed to raising an
+/// unhandled error - a tool-call failure alone doesn't stop the script; Lua
+/// code gets `(ok, result_or_error)` back from `office:call` and decides for
+/// itself whether to continue).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptTranscript {
+    pub steps: Vec<ScriptStepResult>,
+    pub completed: bool,
+    pub error: Option<String>,
+}
+
+/// Run `source` against `client`, exposing it to the script as the bound
+/// `office` object with a single `office:call(tool_name, arguments)` method.
+pub async fn run_script(
+    client: Arc<MCPClient>,
+    source: String,
+) -> Result<ScriptTranscript, LibreOfficeError> {
+    let steps = Arc::new(Mutex::new(Vec::<ScriptStepResult>::new()));
+
+    let lua = new_sandboxed_lua().map_err(|e| LibreOfficeError::ScriptError(e.to_string()))?;
+    register_office(&lua, client, Arc::clone(&steps))
+        .map_err(|e| LibreOfficeError::ScriptError(e.to_string()))?;
+
+    let outcome = lua.load(&source).exec_async().await;
+    let steps = steps.lock().expect("script transcript mutex poisoned").clone();
+
+    match outcome {
+        Ok(()) => Ok(ScriptTranscript {
+            steps,
+            completed: true,
+            error: None,
+        }),
+        Err(e) => {
+            log::warn!("LibreOffice script did not complete: {e}");
+            Ok(ScriptTranscript {
+                steps,
+                completed: false,
+                error: Some(e.to_string()),
+            })
+        }
+    }
+}
+
+/// Construct a Lua interpreter with only the "safe" standard libraries
+/// loaded (`coroutine`/`table`/`string`/`utf8`/`math`) - `Lua::new()` loads
+/// everything, including `os`/`io`/`package`/`debug`, which would let a
+/// frontend-supplied script source (`libreoffice_run_script` takes it as a
+/// plain string, no allowlist) reach `os.execute`/`io.open` for arbitrary
+/// shell/file access. `office:call` is the only thing scripts need.
+fn new_sandboxed_lua() -> mlua::Result<Lua> {
+    Lua::new_with(
+        StdLib::COROUTINE | StdLib::TABLE | StdLib::STRING | StdLib::UTF8 | StdLib::MATH,
+        LuaOptions::default(),
+    )
+}
+
+/// Bind the `office` global table, with a `call` method that runs an MCP tool
+/// call on `client` and reports `(ok, result_or_error)` back to the script.
+fn register_office(
+    lua: &Lua,
+    client: Arc<MCPClient>,
+    steps: Arc<Mutex<Vec<ScriptStepResult>>>,
+) -> mlua::Result<()> {
+    let office = lua.create_table()?;
+
+    let call = lua.create_async_function(
+        move |lua, (_office, tool_name, arguments): (Table, String, Option<LuaValue>)| {
+            let client = Arc::clone(&client);
+            let steps = Arc::clone(&steps);
+            async move {
+                let args = match arguments {
+                    Some(value) => lua.from_value(value)?,
+                    None => serde_json::json!({}),
+                };
+
+                let step = match client.call_tool(&tool_name, args).await {
+                    Ok(result) => ScriptStepResult {
+                        tool_name: tool_name.clone(),
+                        success: true,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(e) => ScriptStepResult {
+                        tool_name: tool_name.clone(),
+                        success: false,
+                        result: None,
+                        error: Some(e.to_string()),
+                    },
+                };
+
+                let ok = step.success;
+                let reported = if step.success {
+                    lua.to_value(&step.result)?
+                } else {
+                    LuaValue::String(lua.create_string(step.error.as_deref().unwrap_or(""))?)
+                };
+                steps.lock().expect("script transcript mutex poisoned").push(step);
+
+                Ok((ok, reported))
+            }
+        },
+    )?;
+
+    office.set("call", call)?;
+    lua.globals().set("office", office)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandboxed_lua_has_no_os_io_package_debug() {
+        let lua = new_sandboxed_lua().expect("failed to construct sandboxed Lua");
+        let globals = lua.globals();
+
+        for forbidden in ["os", "io", "package", "debug"] {
+            let value: LuaValue = globals
+                .get(forbidden)
+                .expect("reading an unset global should not error");
+            assert!(
+                value.is_nil(),
+                "global `{forbidden}` should not be loaded in a sandboxed script, found {value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sandboxed_lua_rejects_os_execute_and_io_open() {
+        let lua = new_sandboxed_lua().expect("failed to construct sandboxed Lua");
+
+        let os_execute = lua.load("return os.execute('true')").exec();
+        assert!(os_execute.is_err(), "os.execute should be unreachable without the os stdlib");
+
+        let io_open = lua.load("return io.open('/etc/passwd')").exec();
+        assert!(io_open.is_err(), "io.open should be unreachable without the io stdlib");
+    }
+
+    #[test]
+    fn test_sandboxed_lua_keeps_safe_libs_for_office_scripts() {
+        let lua = new_sandboxed_lua().expect("failed to construct sandboxed Lua");
+        // table/string/math (used by office:call argument tables and any
+        // script-side formatting) should still work.
+        lua.load("local t = { a = 1 }; return string.format('%d', t.a) + math.abs(-1)")
+            .exec()
+            .expect("safe stdlib (table/string/math) should remain available");
+    }
+}
@@ -0,0 +1,1145 @@
+//! Async transport for the MCP JSON-RPC connection, modeled on the helix-lsp
+//! transport pattern: a dedicated reader task drains the child process's stdout,
+//! and for every framed message it parses either a response or a notification.
+//! Responses are routed back to their caller by request id via a
+//! `pending_requests: HashMap<u64, oneshot::Sender<Result<Value, JsonRpcError>>>`,
+//! so callers are no longer required to arrive in request order - a response to
+//! request 3 can land before the response to request 2 if the server answers out
+//! of order or interleaves progress notifications. [`MCPClient`](super::MCPClient)
+//! is a thin, tool-call-shaped API over a single `Transport`.
+//!
+//! stdin and stdout are locked independently of each other (and of
+//! `ProcessManager`, which only ever handles kill/wait/liveness): the reader
+//! task holds sole, un-mutexed ownership of stdout for the lifetime of its read
+//! loop, since nothing else ever reads it, while every write (a tool call, a
+//! `notifications/cancelled`) goes through its own `Mutex<ChildStdin>`. A
+//! pending read blocks indefinitely whenever the server has nothing to say -
+//! sharing one mutex across read and write would serialize every write (and
+//! every cancellation) behind whatever the reader happens to be blocked on.
+
+use crate::libreoffice::process_manager::ProcessManager;
+use crate::libreoffice::types::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, ChildStdout};
+use tokio::sync::{broadcast, oneshot, Mutex, Notify, Semaphore};
+use tokio::time::{timeout, Duration};
+
+/// Capacity of the server-notification broadcast channel. Lagging subscribers drop
+/// the oldest notifications rather than stalling the reader task.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
+
+/// JSON-RPC error code for a method with no registered handler, per the spec's
+/// reserved range.
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// JSON-RPC error code used internally to mark a pending request's outcome as
+/// cancelled rather than server-rejected, matching the LSP spec's reserved
+/// `RequestCancelled` code. Never sent over the wire - it only ever flows
+/// through a `pending_requests` oneshot.
+const REQUEST_CANCELLED: i32 = -32800;
+
+/// Maximum number of tool calls/requests allowed in flight at once. The Python
+/// MCP server talks to us over a single stdio pipe, so unbounded concurrent
+/// `send` calls would just queue up writes without actually parallelizing
+/// anything useful - this caps how many callers can be waiting on a response at
+/// once, and makes the rest queue on `acquire` instead.
+const MAX_IN_FLIGHT_REQUESTS: usize = 16;
+
+/// Outcome of a request as routed back to its caller by the reader task: the
+/// tool result, or the JSON-RPC error object the server returned for it.
+type PendingResult = Result<Value, JsonRpcError>;
+
+/// Future returned by a registered server-request handler.
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<Value, JsonRpcError>> + Send>>;
+
+/// Handler for a server-initiated request, registered via [`Transport::on_request`].
+type RequestHandler = Box<dyn Fn(Value) -> HandlerFuture + Send + Sync>;
+
+/// Wire framing used to delimit JSON-RPC messages on the MCP server's stdio pipes.
+///
+/// The bundled Python server speaks newline-delimited JSON, but MCP servers built on
+/// other SDKs (and the LSP-derived tooling some of them share code with) frame
+/// messages with `Content-Length`/`Content-Type` headers instead, so this is pluggable
+/// per client rather than hardcoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Framing {
+    /// One JSON-RPC message per line (the Python MCP server's default behavior)
+    #[default]
+    LineDelimited,
+    /// LSP-style `Content-Length: N\r\n\r\n<N bytes of JSON>` framing
+    HeaderDelimited,
+}
+
+/// Cooperative cancellation handle for an in-flight [`Transport::send`] call.
+/// Cloning shares the same underlying signal, so a token handed to a caller and
+/// cancelled there is observed by the in-flight request it was created for.
+#[derive(Clone)]
+pub struct CancellationToken {
+    notify: Arc<Notify>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self {
+            notify: Arc::new(Notify::new()),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Signal cancellation. Safe to call more than once, and safe to call after the
+    /// request it was created for has already completed.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once `cancel()` has been called.
+    async fn cancelled(&self) {
+        if self.is_cancelled() {
+            return;
+        }
+        self.notify.notified().await;
+    }
+}
+
+/// Typed server-initiated events recognized from raw notification method names,
+/// layered over the raw [`Transport::notifications`] broadcast so a Tauri UI can
+/// live-refresh (re-list tools, re-fetch a changed resource) rather than
+/// polling, instead of pattern-matching method strings itself. Notifications
+/// with no recognized method aren't published here - subscribe to
+/// `notifications()` directly to observe those.
+#[derive(Debug, Clone)]
+pub enum McpEvent {
+    /// `notifications/tools/list_changed`: the tool list should be re-fetched.
+    ToolsListChanged,
+    /// `notifications/resources/list_changed`: the resource list should be re-fetched.
+    ResourcesListChanged,
+    /// `notifications/resources/updated`: the subscribed resource at `uri` changed.
+    ResourceUpdated { uri: String },
+    /// `notifications/prompts/list_changed`: the prompt list should be re-fetched.
+    PromptsListChanged,
+}
+
+impl McpEvent {
+    /// Recognize a typed event from a raw notification's method (and, for
+    /// `resources/updated`, its `uri` param), if any (internal helper).
+    fn from_notification(notification: &JsonRpcNotification) -> Option<Self> {
+        match notification.method.as_str() {
+            "notifications/tools/list_changed" => Some(Self::ToolsListChanged),
+            "notifications/resources/list_changed" => Some(Self::ResourcesListChanged),
+            "notifications/resources/updated" => {
+                let uri = notification.params.get("uri")?.as_str()?.to_string();
+                Some(Self::ResourceUpdated { uri })
+            }
+            "notifications/prompts/list_changed" => Some(Self::PromptsListChanged),
+            _ => None,
+        }
+    }
+}
+
+/// The MCP JSON-RPC connection: owns the child process, the next-request-id
+/// counter, and the reader task that demultiplexes its stdout.
+///
+/// Constructed and driven by [`MCPClient`](super::MCPClient), which layers the
+/// `initialize`/tool-call API on top of [`Transport::send`].
+pub struct Transport {
+    /// Control-plane only (kill/wait/liveness/log-tail) - never touches stdin/stdout,
+    /// so locking it never blocks behind a pending read or a write.
+    process: Arc<Mutex<ProcessManager>>,
+    /// Write half of the child's stdio pipes, locked independently of stdout (which
+    /// the reader task owns outright) so a write is never stuck behind a pending read.
+    stdin: Arc<Mutex<ChildStdin>>,
+    next_id: Arc<AtomicU64>,
+    pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+    /// Bounds the number of `send` calls allowed to be in flight at once; see
+    /// `MAX_IN_FLIGHT_REQUESTS`.
+    in_flight: Arc<Semaphore>,
+    framing: Framing,
+    notification_tx: broadcast::Sender<JsonRpcNotification>,
+    event_tx: broadcast::Sender<McpEvent>,
+    request_handlers: Arc<Mutex<HashMap<String, RequestHandler>>>,
+    supervised: bool,
+    restart_count: Arc<AtomicU64>,
+    last_crash: Arc<Mutex<Option<String>>>,
+    /// Cleared while a handshake (initial or post-crash) is in flight; `send`
+    /// waits on `init_gate` until this is set so requests issued during that window
+    /// queue instead of racing the handshake.
+    initialized: Arc<AtomicBool>,
+    init_gate: Arc<Notify>,
+    /// Set by `shutdown()` before it kills the process, and checked by the reader
+    /// task alongside `supervised` before respawning - without this, a supervised
+    /// transport's reader task sees the killed process's stdout close and
+    /// transparently respawns it right after the caller asked to shut down.
+    shutting_down: Arc<AtomicBool>,
+}
+
+impl Transport {
+    /// Spawn the Python MCP server process, run the initialize/initialized
+    /// handshake, and start the background reader task. Returns the transport
+    /// alongside the parsed `InitializeResult` so the caller can keep it around
+    /// without holding a lock.
+    ///
+    /// When `supervised` is true, the reader task respawns the process and
+    /// re-runs the handshake if it ever observes the process crash (EOF on
+    /// stdout), instead of leaving the transport permanently dead.
+    pub async fn spawn(
+        framing: Framing,
+        supervised: bool,
+    ) -> Result<(Self, InitializeResult), LibreOfficeError> {
+        let (process, stdin, stdout) = ProcessManager::spawn().await?;
+        Self::spawn_with_process(process, stdin, stdout, framing, supervised).await
+    }
+
+    /// Like [`Transport::spawn`], but peeks the first bytes of the server's stdout
+    /// to guess its wire framing instead of requiring the caller to already know it.
+    /// Useful against MCP servers other than the bundled Python one, whose framing
+    /// isn't known ahead of time. Costs one stdout peek before the handshake; prefer
+    /// `spawn` with an explicit `Framing` when it's already known.
+    pub async fn spawn_autodetect(
+        supervised: bool,
+    ) -> Result<(Self, InitializeResult), LibreOfficeError> {
+        let (process, stdin, mut stdout) = ProcessManager::spawn().await?;
+        let framing = Self::detect_framing(&mut stdout).await?;
+        log::info!("Detected {:?} framing from MCP server stdout", framing);
+        Self::spawn_with_process(process, stdin, stdout, framing, supervised).await
+    }
+
+    /// Peek at the first bytes of `stdout` without consuming them, and guess which
+    /// `Framing` the server speaks: a message starting with `Content-Length:` is
+    /// LSP-style header-delimited, anything else is assumed to be a bare JSON line
+    /// (ndjson), matching the bundled Python server's default (internal helper for
+    /// [`Transport::spawn_autodetect`]).
+    async fn detect_framing(stdout: &mut BufReader<ChildStdout>) -> Result<Framing, LibreOfficeError> {
+        const HEADER_PREFIX: &[u8] = b"Content-Length:";
+
+        let buf = stdout.fill_buf().await.map_err(|e| {
+            LibreOfficeError::ProcessCrashed(format!(
+                "Failed to peek stdout for framing detection: {}",
+                e
+            ))
+        })?;
+
+        if buf.is_empty() {
+            return Err(LibreOfficeError::ProcessCrashed(
+                "Process stdout closed before any message could be read for framing detection"
+                    .to_string(),
+            ));
+        }
+
+        Ok(if buf.starts_with(HEADER_PREFIX) {
+            Framing::HeaderDelimited
+        } else {
+            Framing::LineDelimited
+        })
+    }
+
+    /// Run the initialize handshake against an already-spawned process and start the
+    /// background reader task (internal helper shared by `spawn`/`spawn_autodetect`).
+    async fn spawn_with_process(
+        process: ProcessManager,
+        stdin: ChildStdin,
+        stdout: BufReader<ChildStdout>,
+        framing: Framing,
+        supervised: bool,
+    ) -> Result<(Self, InitializeResult), LibreOfficeError> {
+        log::info!(
+            "Creating new MCP transport (framing: {:?}, supervised: {})...",
+            framing,
+            supervised
+        );
+        log::info!("Python MCP server spawned successfully");
+
+        let process = Arc::new(Mutex::new(process));
+        let stdin = Arc::new(Mutex::new(stdin));
+        let mut stdout = stdout;
+        let next_id = Arc::new(AtomicU64::new(1));
+        let pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let in_flight = Arc::new(Semaphore::new(MAX_IN_FLIGHT_REQUESTS));
+        let (notification_tx, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let (event_tx, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let request_handlers: Arc<Mutex<HashMap<String, RequestHandler>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let restart_count = Arc::new(AtomicU64::new(0));
+        let last_crash: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        let initialized = Arc::new(AtomicBool::new(false));
+        let init_gate = Arc::new(Notify::new());
+        let shutting_down = Arc::new(AtomicBool::new(false));
+
+        let server_info = Self::handshake(&stdin, &mut stdout, framing, &next_id).await?;
+        initialized.store(true, Ordering::SeqCst);
+        init_gate.notify_waiters();
+
+        let process_clone = Arc::clone(&process);
+        let stdin_clone = Arc::clone(&stdin);
+        let next_id_clone = Arc::clone(&next_id);
+        let pending_clone = Arc::clone(&pending_requests);
+        let notification_tx_clone = notification_tx.clone();
+        let event_tx_clone = event_tx.clone();
+        let handlers_clone = Arc::clone(&request_handlers);
+        let restart_count_clone = Arc::clone(&restart_count);
+        let last_crash_clone = Arc::clone(&last_crash);
+        let initialized_clone = Arc::clone(&initialized);
+        let init_gate_clone = Arc::clone(&init_gate);
+        let shutting_down_clone = Arc::clone(&shutting_down);
+        tokio::spawn(Self::response_reader_task(
+            process_clone,
+            stdin_clone,
+            stdout,
+            framing,
+            next_id_clone,
+            pending_clone,
+            notification_tx_clone,
+            event_tx_clone,
+            handlers_clone,
+            supervised,
+            restart_count_clone,
+            last_crash_clone,
+            initialized_clone,
+            init_gate_clone,
+            shutting_down_clone,
+        ));
+
+        log::info!("MCP transport ready");
+
+        let transport = Self {
+            process,
+            stdin,
+            next_id,
+            pending_requests,
+            in_flight,
+            framing,
+            notification_tx,
+            event_tx,
+            request_handlers,
+            supervised,
+            restart_count,
+            last_crash,
+            initialized,
+            init_gate,
+            shutting_down,
+        };
+
+        Ok((transport, server_info))
+    }
+
+    /// Wait until an in-flight handshake (initial construction has already finished
+    /// by the time any caller can observe `self`, but a post-crash re-handshake under
+    /// a supervised transport has not) completes. Uses the standard
+    /// check-then-register-then-recheck pattern so a `notify_waiters` that races with
+    /// this call is never missed.
+    async fn wait_for_init(initialized: &AtomicBool, init_gate: &Notify) {
+        if initialized.load(Ordering::SeqCst) {
+            return;
+        }
+        let notified = init_gate.notified();
+        if initialized.load(Ordering::SeqCst) {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Run the initialize/initialized handshake against whatever process
+    /// `process` currently holds, returning the parsed `InitializeResult`. Used both
+    /// at construction time and to re-handshake after an auto-restart respawn
+    /// (internal helper).
+    async fn handshake(
+        stdin: &Arc<Mutex<ChildStdin>>,
+        stdout: &mut BufReader<ChildStdout>,
+        framing: Framing,
+        next_id: &AtomicU64,
+    ) -> Result<InitializeResult, LibreOfficeError> {
+        let init_id = next_id.fetch_add(1, Ordering::SeqCst);
+        let init_request = JsonRpcRequest::new(
+            init_id,
+            "initialize",
+            serde_json::to_value(InitializeParams::default())?,
+        );
+
+        Self::send_request_internal(stdin, framing, &init_request).await?;
+        log::info!("Sent initialize request");
+
+        let init_response = loop {
+            let message = Self::read_message(stdout, framing).await?;
+            match serde_json::from_str::<JsonRpcResponse>(&message) {
+                Ok(response) => break response,
+                Err(_) => {
+                    log::debug!("Skipping non-response message from MCP server: {}", message.trim());
+                    continue;
+                }
+            }
+        };
+        log::info!("Received initialize response");
+
+        let server_info = if let Some(result) = init_response.result {
+            serde_json::from_value::<InitializeResult>(result).map_err(|e| {
+                LibreOfficeError::HandshakeFailed(format!("Invalid initialize response: {}", e))
+            })?
+        } else if let Some(error) = init_response.error {
+            return Err(LibreOfficeError::HandshakeFailed(format!(
+                "Initialize failed: {} (code {})",
+                error.message, error.code
+            )));
+        } else {
+            return Err(LibreOfficeError::HandshakeFailed(
+                "Initialize response missing result and error".to_string(),
+            ));
+        };
+
+        log::info!(
+            "MCP server initialized: {} v{}",
+            server_info.server_info.name,
+            server_info.server_info.version
+        );
+
+        let init_notification = JsonRpcNotification::new("notifications/initialized", json!({}));
+        Self::send_notification_internal(stdin, framing, &init_notification).await?;
+        log::info!("Sent initialized notification");
+
+        Ok(server_info)
+    }
+
+    /// Number of times the MCP server process has been automatically respawned after
+    /// a crash. Always 0 unless this transport was spawned with `supervised: true`.
+    pub fn restart_count(&self) -> u64 {
+        self.restart_count.load(Ordering::SeqCst)
+    }
+
+    /// The most recent crash/restart failure observed by a supervised transport, if
+    /// any. Cleared on the next successful respawn.
+    pub async fn last_crash(&self) -> Option<String> {
+        self.last_crash.lock().await.clone()
+    }
+
+    /// Register a handler for a server-initiated request method (e.g. a sampling
+    /// callback the server invokes on this client). Replaces any existing handler for
+    /// the same method. Methods with no registered handler automatically receive a
+    /// JSON-RPC "method not found" error response.
+    pub async fn on_request<F>(
+        &self,
+        method: impl Into<String>,
+        handler: impl Fn(Value) -> F + Send + Sync + 'static,
+    ) where
+        F: Future<Output = Result<Value, JsonRpcError>> + Send + 'static,
+    {
+        let handler: RequestHandler = Box::new(move |params| Box::pin(handler(params)));
+        let mut handlers = self.request_handlers.lock().await;
+        handlers.insert(method.into(), handler);
+    }
+
+    /// Subscribe to server-initiated notifications (e.g. `notifications/tools/list_changed`).
+    ///
+    /// Each call returns an independent receiver; a subscriber that falls behind
+    /// `NOTIFICATION_CHANNEL_CAPACITY` messages sees `RecvError::Lagged` rather than
+    /// blocking the reader task.
+    pub fn notifications(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.notification_tx.subscribe()
+    }
+
+    /// Subscribe to typed [`McpEvent`]s recognized from server notifications - a
+    /// narrower, UI-friendly alternative to [`Transport::notifications`] for
+    /// consumers that only care about `listChanged`/`updated` capability events.
+    pub fn events(&self) -> broadcast::Receiver<McpEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Send a JSON-RPC request and wait for its response, with an explicit timeout and
+    /// an optional cancellation token.
+    ///
+    /// This handles:
+    /// 1. Generating the request id
+    /// 2. Registering a oneshot in `pending_requests`, keyed on that id, so the
+    ///    reader task can route the response back here whenever it arrives - in
+    ///    particular, out of order relative to other concurrent `send` calls
+    /// 3. Writing the request
+    /// 4. Waiting for the response, cancellation, or timeout, whichever comes first
+    ///
+    /// On cancellation or timeout, the pending entry is removed from
+    /// `pending_requests` and a `notifications/cancelled` notification carrying the
+    /// original request id is sent so the server can abort the corresponding work.
+    ///
+    /// Waits for `init_gate` before generating an id or writing anything, so a call
+    /// issued while a handshake (initial or post-crash) is in flight queues rather
+    /// than racing it.
+    ///
+    /// Also waits to acquire a permit from the in-flight semaphore first, so at
+    /// most `MAX_IN_FLIGHT_REQUESTS` calls are ever waiting on a response from the
+    /// single stdio pipe at once; callers beyond that queue here instead of all
+    /// writing to the pipe at the same time.
+    pub async fn send(
+        &self,
+        method: impl Into<String>,
+        params: Value,
+        request_timeout: Duration,
+        cancel: Option<CancellationToken>,
+    ) -> Result<Value, LibreOfficeError> {
+        let _permit = self
+            .in_flight
+            .acquire()
+            .await
+            .expect("in-flight semaphore is never closed");
+
+        Self::wait_for_init(&self.initialized, &self.init_gate).await;
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let request = JsonRpcRequest::new(id, method, params);
+
+        let (tx, rx) = oneshot::channel();
+
+        {
+            let mut pending = self.pending_requests.lock().await;
+            pending.insert(id, tx);
+        }
+
+        Self::send_request_internal(&self.stdin, self.framing, &request).await?;
+
+        match cancel {
+            Some(cancel) => {
+                tokio::select! {
+                    result = timeout(request_timeout, rx) => {
+                        Self::resolve_request_result(
+                            result,
+                            &self.pending_requests,
+                            &self.stdin,
+                            self.framing,
+                            id,
+                            request_timeout,
+                        )
+                        .await
+                    }
+                    _ = cancel.cancelled() => {
+                        Self::cancel_request(
+                            &self.pending_requests,
+                            &self.stdin,
+                            self.framing,
+                            id,
+                            "cancelled by caller",
+                        )
+                        .await;
+                        Err(LibreOfficeError::Cancelled(id))
+                    }
+                }
+            }
+            None => {
+                Self::resolve_request_result(
+                    timeout(request_timeout, rx).await,
+                    &self.pending_requests,
+                    &self.stdin,
+                    self.framing,
+                    id,
+                    request_timeout,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Turn a `timeout(rx).await` outcome into a tool result or an error, cleaning up
+    /// the pending-request entry and notifying the server on timeout (internal helper)
+    async fn resolve_request_result(
+        result: Result<Result<PendingResult, oneshot::error::RecvError>, tokio::time::error::Elapsed>,
+        pending_requests: &Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+        stdin: &Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        id: u64,
+        request_timeout: Duration,
+    ) -> Result<Value, LibreOfficeError> {
+        match result {
+            Ok(Ok(Ok(value))) => Ok(value),
+            Ok(Ok(Err(error))) if error.code == REQUEST_CANCELLED => {
+                Err(LibreOfficeError::Cancelled(id))
+            }
+            Ok(Ok(Err(error))) => Err(LibreOfficeError::JsonRpcError {
+                code: error.code,
+                message: error.message,
+            }),
+            Ok(Err(_)) => Err(LibreOfficeError::InvalidResponse(
+                "Response channel closed".to_string(),
+            )),
+            Err(_) => {
+                Self::cancel_request(pending_requests, stdin, framing, id, "timeout").await;
+                Err(LibreOfficeError::Timeout(request_timeout.as_secs()))
+            }
+        }
+    }
+
+    /// Remove a pending request and tell the server to stop working on it, via a
+    /// `notifications/cancelled` notification carrying the original request id and a
+    /// human-readable reason (internal helper)
+    async fn cancel_request(
+        pending_requests: &Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+        stdin: &Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        id: u64,
+        reason: &str,
+    ) {
+        let removed = {
+            let mut pending = pending_requests.lock().await;
+            pending.remove(&id)
+        };
+
+        if removed.is_none() {
+            // The response raced in right as we decided to cancel; nothing to clean up.
+            return;
+        }
+
+        let notification = JsonRpcNotification::new(
+            "notifications/cancelled",
+            json!({ "requestId": id, "reason": reason }),
+        );
+        if let Err(e) = Self::send_notification_internal(stdin, framing, &notification).await {
+            log::warn!(
+                "Failed to send cancellation notification for request {}: {}",
+                id,
+                e
+            );
+        }
+    }
+
+    /// Write a single framed message to the process's stdin, per `framing`
+    /// (internal helper). Every write is flushed immediately, so - unlike a
+    /// buffered writer left to fill on its own schedule - a request is always
+    /// fully on the wire by the time this returns.
+    ///
+    /// Locks only `stdin`, independently of `stdout` (owned outright by the reader
+    /// task) and of `ProcessManager` (control-plane only) - a write, including a
+    /// time-critical `notifications/cancelled`, is never stuck behind a pending read.
+    async fn write_framed(
+        stdin: &Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        json: &str,
+    ) -> Result<(), LibreOfficeError> {
+        let mut stdin = stdin.lock().await;
+        match framing {
+            Framing::LineDelimited => {
+                stdin.write_all(json.as_bytes()).await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to write to stdin: {}", e))
+                })?;
+                stdin.write_all(b"\n").await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to write newline: {}", e))
+                })?;
+            }
+            Framing::HeaderDelimited => {
+                let header = format!(
+                    "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n",
+                    json.len()
+                );
+                stdin.write_all(header.as_bytes()).await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to write header: {}", e))
+                })?;
+                stdin.write_all(json.as_bytes()).await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to write to stdin: {}", e))
+                })?;
+            }
+        }
+        stdin.flush().await.map_err(|e| {
+            LibreOfficeError::ProcessCrashed(format!("Failed to flush stdin: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Send a JSON-RPC request (internal helper)
+    async fn send_request_internal(
+        stdin: &Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        request: &JsonRpcRequest,
+    ) -> Result<(), LibreOfficeError> {
+        let json = serde_json::to_string(request)?;
+        Self::write_framed(stdin, framing, &json).await
+    }
+
+    /// Send a JSON-RPC notification (internal helper)
+    async fn send_notification_internal(
+        stdin: &Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        notification: &JsonRpcNotification,
+    ) -> Result<(), LibreOfficeError> {
+        let json = serde_json::to_string(notification)?;
+        Self::write_framed(stdin, framing, &json).await
+    }
+
+    /// Read the headers of a `HeaderDelimited` message (everything up to the blank
+    /// line), returning them as a case-sensitive map (internal helper)
+    async fn read_headers(
+        stdout: &mut BufReader<ChildStdout>,
+    ) -> Result<HashMap<String, String>, LibreOfficeError> {
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            let bytes_read = stdout.read_line(&mut line).await.map_err(|e| {
+                LibreOfficeError::ProcessCrashed(format!("Failed to read header: {}", e))
+            })?;
+
+            if bytes_read == 0 {
+                return Err(LibreOfficeError::ProcessCrashed(
+                    "Process stdout closed while reading headers".to_string(),
+                ));
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                return Ok(headers);
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    /// Read one raw JSON-RPC message body from stdout according to `framing`, skipping
+    /// non-JSON lines in `LineDelimited` mode (internal helper)
+    async fn read_message(
+        stdout: &mut BufReader<ChildStdout>,
+        framing: Framing,
+    ) -> Result<String, LibreOfficeError> {
+        match framing {
+            Framing::LineDelimited => loop {
+                let mut line = String::new();
+                let bytes_read = stdout.read_line(&mut line).await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to read from stdout: {}", e))
+                })?;
+
+                if bytes_read == 0 {
+                    return Err(LibreOfficeError::ProcessCrashed(
+                        "Process stdout closed".to_string(),
+                    ));
+                }
+
+                if serde_json::from_str::<Value>(&line).is_ok() {
+                    return Ok(line);
+                }
+
+                log::debug!("Skipping non-JSON line from MCP server: {}", line.trim());
+            },
+            Framing::HeaderDelimited => {
+                let headers = Self::read_headers(stdout).await?;
+                let content_length: usize = headers
+                    .get("Content-Length")
+                    .ok_or_else(|| {
+                        LibreOfficeError::ProcessCrashed(
+                            "Header-delimited message missing Content-Length".to_string(),
+                        )
+                    })?
+                    .parse()
+                    .map_err(|e| {
+                        LibreOfficeError::ProcessCrashed(format!(
+                            "Invalid Content-Length header: {}",
+                            e
+                        ))
+                    })?;
+
+                let mut body = vec![0u8; content_length];
+                stdout.read_exact(&mut body).await.map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Failed to read message body: {}", e))
+                })?;
+
+                String::from_utf8(body).map_err(|e| {
+                    LibreOfficeError::ProcessCrashed(format!("Message body not UTF-8: {}", e))
+                })
+            }
+        }
+    }
+
+    /// Reply to a server-initiated request, dispatching to a registered handler or
+    /// falling back to a JSON-RPC "method not found" error (internal helper)
+    async fn handle_server_request(
+        stdin: Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        request_handlers: Arc<Mutex<HashMap<String, RequestHandler>>>,
+        request: JsonRpcRequest,
+    ) {
+        let handler_result = {
+            let handlers = request_handlers.lock().await;
+            handlers.get(&request.method).map(|h| h(request.params.clone()))
+        };
+
+        let response = match handler_result {
+            Some(fut) => match fut.await {
+                Ok(result) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(error) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: None,
+                    error: Some(error),
+                },
+            },
+            None => {
+                log::warn!("No handler registered for server request method: {}", request.method);
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: METHOD_NOT_FOUND,
+                        message: format!("Method not found: {}", request.method),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let json = match serde_json::to_string(&response) {
+            Ok(json) => json,
+            Err(e) => {
+                log::error!("Failed to serialize server request response: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = Self::write_framed(&stdin, framing, &json).await {
+            log::error!("Failed to send server request response: {}", e);
+        }
+    }
+
+    /// Background task to read responses, notifications, and server-initiated
+    /// requests from stdout
+    ///
+    /// This runs in a separate task and:
+    /// 1. Continuously reads framed messages from stdout
+    /// 2. Distinguishes responses (`id`, no `method`), notifications (`method`, no
+    ///    `id`), and server-initiated requests (`id` and `method`) by shape, since the
+    ///    MCP wire format has no message-type tag
+    /// 3. Matches response IDs to pending requests, fans notifications out to
+    ///    `notification_tx` subscribers, and dispatches requests to `request_handlers`
+    #[allow(clippy::too_many_arguments)]
+    async fn response_reader_task(
+        process: Arc<Mutex<ProcessManager>>,
+        stdin: Arc<Mutex<ChildStdin>>,
+        mut stdout: BufReader<ChildStdout>,
+        framing: Framing,
+        next_id: Arc<AtomicU64>,
+        pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+        notification_tx: broadcast::Sender<JsonRpcNotification>,
+        event_tx: broadcast::Sender<McpEvent>,
+        request_handlers: Arc<Mutex<HashMap<String, RequestHandler>>>,
+        supervised: bool,
+        restart_count: Arc<AtomicU64>,
+        last_crash: Arc<Mutex<Option<String>>>,
+        initialized: Arc<AtomicBool>,
+        init_gate: Arc<Notify>,
+        shutting_down: Arc<AtomicBool>,
+    ) {
+        log::info!("Response reader task started");
+
+        loop {
+            // No locking here: this task holds sole, un-mutexed ownership of `stdout`
+            // for its entire lifetime, so a pending read never contends with a write.
+            let read_result = Self::read_message(&mut stdout, framing).await;
+
+            match read_result {
+                Ok(message) => match serde_json::from_str::<Value>(&message) {
+                    Ok(value) if value.get("id").is_some() && value.get("method").is_some() => {
+                        match serde_json::from_value::<JsonRpcRequest>(value) {
+                            Ok(request) => {
+                                log::debug!("Received server request: {}", request.method);
+                                let stdin_clone = Arc::clone(&stdin);
+                                let handlers_clone = Arc::clone(&request_handlers);
+                                tokio::spawn(Self::handle_server_request(
+                                    stdin_clone,
+                                    framing,
+                                    handlers_clone,
+                                    request,
+                                ));
+                            }
+                            Err(e) => {
+                                log::debug!("Message with id+method was not a valid request: {}", e);
+                            }
+                        }
+                    }
+                    Ok(value) if value.get("id").is_some() => {
+                        match serde_json::from_value::<JsonRpcResponse>(value) {
+                            Ok(response) => {
+                                log::debug!("Received response for request ID {}", response.id);
+
+                                let sender = {
+                                    let mut pending = pending_requests.lock().await;
+                                    pending.remove(&response.id)
+                                };
+
+                                if let Some(sender) = sender {
+                                    let outcome: PendingResult = match response.error {
+                                        Some(error) => Err(error),
+                                        None => response.result.ok_or_else(|| JsonRpcError {
+                                            code: -32000,
+                                            message: "Response missing result".to_string(),
+                                            data: None,
+                                        }),
+                                    };
+                                    if sender.send(outcome).is_err() {
+                                        log::warn!("Failed to send response - receiver dropped");
+                                    }
+                                } else {
+                                    log::warn!(
+                                        "Received response for unknown request ID {}",
+                                        response.id
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                log::debug!("Message with id was not a valid response: {}", e);
+                            }
+                        }
+                    }
+                    Ok(value) if value.get("method").is_some() => {
+                        match serde_json::from_value::<JsonRpcNotification>(value) {
+                            Ok(notification) => {
+                                log::debug!("Received notification: {}", notification.method);
+                                if let Some(event) = McpEvent::from_notification(&notification) {
+                                    // No subscribers is not an error - only log at debug.
+                                    if event_tx.send(event).is_err() {
+                                        log::debug!("No subscribers for typed MCP event");
+                                    }
+                                }
+                                // No subscribers is not an error - only log at debug.
+                                if notification_tx.send(notification).is_err() {
+                                    log::debug!("No subscribers for server notification");
+                                }
+                            }
+                            Err(e) => {
+                                log::debug!("Message with method was not a valid notification: {}", e);
+                            }
+                        }
+                    }
+                    Ok(_) => {
+                        log::debug!("Ignoring message with neither id nor method: {}", message.trim());
+                    }
+                    Err(e) => {
+                        // Might be a log message that slipped through line-based framing
+                        log::debug!("Non-JSON-RPC message from stdout: {} ({})", message.trim(), e);
+                    }
+                },
+                Err(e) => {
+                    log::error!("Error reading from stdout: {}", e);
+                    break;
+                }
+            }
+        }
+
+        log::info!("Response reader task exited");
+
+        // The server is gone (or its stdout is), so every request still waiting on a
+        // response from it needs to fail now rather than idle out its own timeout.
+        Self::drain_pending_requests(&pending_requests, "MCP server process crashed or closed its stdout").await;
+
+        if supervised && !shutting_down.load(Ordering::SeqCst) {
+            // Close the gate so requests issued while we respawn and re-handshake
+            // queue instead of writing to a process that's being replaced out from
+            // under them.
+            initialized.store(false, Ordering::SeqCst);
+
+            Self::attempt_restart(
+                process,
+                stdin,
+                framing,
+                next_id,
+                pending_requests,
+                notification_tx,
+                event_tx,
+                request_handlers,
+                restart_count,
+                last_crash,
+                initialized,
+                init_gate,
+                shutting_down,
+            )
+            .await;
+        }
+    }
+
+    /// Fail every in-flight request with a crash-equivalent JSON-RPC error instead of
+    /// leaving callers to idle out their own timeout (internal helper)
+    async fn drain_pending_requests(
+        pending_requests: &Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+        reason: &str,
+    ) {
+        let mut pending = pending_requests.lock().await;
+        if pending.is_empty() {
+            return;
+        }
+
+        log::warn!("Failing {} pending request(s): {}", pending.len(), reason);
+        for (id, sender) in pending.drain() {
+            let error = JsonRpcError {
+                code: -32000,
+                message: format!("MCP server process crashed: {}", reason),
+                data: None,
+            };
+            if sender.send(Err(error)).is_err() {
+                log::debug!("Pending request {} receiver already dropped", id);
+            }
+        }
+    }
+
+    /// Respawn the Python server process and re-run the handshake after a crash,
+    /// restarting the reader task on success (internal helper, only invoked for
+    /// supervised transports)
+    #[allow(clippy::too_many_arguments)]
+    async fn attempt_restart(
+        process: Arc<Mutex<ProcessManager>>,
+        stdin: Arc<Mutex<ChildStdin>>,
+        framing: Framing,
+        next_id: Arc<AtomicU64>,
+        pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>>,
+        notification_tx: broadcast::Sender<JsonRpcNotification>,
+        event_tx: broadcast::Sender<McpEvent>,
+        request_handlers: Arc<Mutex<HashMap<String, RequestHandler>>>,
+        restart_count: Arc<AtomicU64>,
+        last_crash: Arc<Mutex<Option<String>>>,
+        initialized: Arc<AtomicBool>,
+        init_gate: Arc<Notify>,
+        shutting_down: Arc<AtomicBool>,
+    ) {
+        log::warn!("Attempting to respawn MCP server and re-run handshake");
+
+        let handshake_result = match ProcessManager::spawn().await {
+            Ok((new_process, new_stdin, mut new_stdout)) => {
+                *process.lock().await = new_process;
+                *stdin.lock().await = new_stdin;
+                match Self::handshake(&stdin, &mut new_stdout, framing, &next_id).await {
+                    Ok(server_info) => Ok((server_info, new_stdout)),
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        match handshake_result {
+            Ok((server_info, new_stdout)) => {
+                let count = restart_count.fetch_add(1, Ordering::SeqCst) + 1;
+                *last_crash.lock().await = None;
+                log::info!(
+                    "MCP server respawned successfully (restart #{}): {} v{}",
+                    count,
+                    server_info.server_info.name,
+                    server_info.server_info.version
+                );
+
+                tokio::spawn(Self::response_reader_task(
+                    process,
+                    stdin,
+                    new_stdout,
+                    framing,
+                    next_id,
+                    pending_requests,
+                    notification_tx,
+                    event_tx,
+                    request_handlers,
+                    true,
+                    restart_count,
+                    last_crash,
+                    Arc::clone(&initialized),
+                    Arc::clone(&init_gate),
+                    shutting_down,
+                ));
+            }
+            Err(e) => {
+                let message = format!("Failed to respawn MCP server: {}", e);
+                log::error!("{}", message);
+                *last_crash.lock().await = Some(message);
+            }
+        }
+
+        // Reopen the gate whether the respawn succeeded or not: on failure there's no
+        // retry loop here, so queued callers proceed and fail naturally against the
+        // still-broken process rather than waiting forever on a gate that will never
+        // reopen.
+        initialized.store(true, Ordering::SeqCst);
+        init_gate.notify_waiters();
+    }
+
+    /// Cancel an in-flight request by id: removes its pending entry, notifies the
+    /// server with a `notifications/cancelled` carrying `{"requestId": id}`, and
+    /// resolves the caller's `send` future with `LibreOfficeError::Cancelled`.
+    ///
+    /// Returns `false` if `id` wasn't (or is no longer) pending - e.g. its
+    /// response already arrived, or it was already cancelled. This is a lower-level
+    /// alternative to passing a [`CancellationToken`] into `send`/`call_tool`, for
+    /// callers that only learn which request to cancel after the fact (e.g. from
+    /// a list of requests a Tauri command tracks by id) rather than holding a
+    /// token from the start.
+    pub async fn cancel(&self, id: u64) -> bool {
+        let removed = {
+            let mut pending = self.pending_requests.lock().await;
+            pending.remove(&id)
+        };
+
+        let Some(sender) = removed else {
+            return false;
+        };
+
+        let notification = JsonRpcNotification::new(
+            "notifications/cancelled",
+            json!({ "requestId": id }),
+        );
+        if let Err(e) = Self::send_notification_internal(&self.stdin, self.framing, &notification).await {
+            log::warn!(
+                "Failed to send cancellation notification for request {}: {}",
+                id,
+                e
+            );
+        }
+
+        if sender
+            .send(Err(JsonRpcError {
+                code: REQUEST_CANCELLED,
+                message: "Request cancelled".to_string(),
+                data: None,
+            }))
+            .is_err()
+        {
+            log::debug!("Cancelled request {} receiver already dropped", id);
+        }
+
+        true
+    }
+
+    /// Check if the MCP server process is still running
+    pub async fn is_running(&self) -> bool {
+        let mut process = self.process.lock().await;
+        process.is_running()
+    }
+
+    /// Shutdown the MCP server
+    pub async fn shutdown(&self) -> Result<(), LibreOfficeError> {
+        log::info!("Shutting down MCP transport...");
+        // Set before killing the process: a supervised transport's reader task sees
+        // the kill as an EOF on stdout and would otherwise transparently respawn the
+        // server right after shutdown, since it has no other way to distinguish a
+        // deliberate shutdown from a crash.
+        self.shutting_down.store(true, Ordering::SeqCst);
+        let mut process = self.process.lock().await;
+        process.kill().await?;
+        log::info!("MCP transport shut down");
+        Ok(())
+    }
+}
+
+impl Drop for Transport {
+    fn drop(&mut self) {
+        log::info!("Transport dropped");
+    }
+}
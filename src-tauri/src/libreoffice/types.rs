@@ -209,6 +209,9 @@ pub enum LibreOfficeError {
     #[error("Python executable not found")]
     PythonNotFound,
 
+    #[error("Python version too old: found {0}, minimum supported is 3.9")]
+    PythonVersionTooOld(String),
+
     #[error("MCP server files not found at path: {0}")]
     ServerFilesNotFound(String),
 
@@ -227,6 +230,9 @@ pub enum LibreOfficeError {
     #[error("Request timeout after {0} seconds")]
     Timeout(u64),
 
+    #[error("Request {0} was cancelled")]
+    Cancelled(u64),
+
     #[error("JSON-RPC error (code {code}): {message}")]
     JsonRpcError { code: i32, message: String },
 
@@ -247,6 +253,10 @@ pub enum LibreOfficeError {
 
     #[error("Service already running")]
     AlreadyRunning,
+
+    #[cfg(feature = "scripting")]
+    #[error("Script error: {0}")]
+    ScriptError(String),
 }
 
 // ============================================================================
@@ -109,6 +109,74 @@ pub fn validate_content_size(content: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Validates that a Unix domain socket path for Ollama is safe to connect to.
+///
+/// A Unix socket has no hostname to check, so this applies the same
+/// allowlist discipline as [`validate_path`] instead: canonicalize the path
+/// (resolving symlinks and `..` components) and require it to live under a
+/// conventional local socket directory, rather than trying to validate it by
+/// string matching.
+///
+/// # Security
+/// - Resolves symlinks before validation (prevents symlink escape)
+/// - Uses an allowlist of runtime-socket directories (`$XDG_RUNTIME_DIR`,
+///   `/run`, `/var/run`, `/tmp`, `$HOME/.ollama`) rather than a blocklist
+/// - A Unix socket is inherently local, but the allowlist still closes off
+///   tricking the app into dialing an arbitrary path on disk
+///
+/// # Errors
+/// Returns error if:
+/// - The path doesn't exist (canonicalize requires existing paths)
+/// - The path is outside every allowed directory
+pub fn validate_socket_path(path_str: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path_str);
+
+    let canonical = std::fs::canonicalize(path).map_err(|e| {
+        log::warn!("Unix socket path canonicalization failed for '{}': {}", path_str, e);
+        format!("Ollama socket not found or inaccessible: {e}")
+    })?;
+
+    let mut allowed_bases = vec![
+        PathBuf::from("/run"),
+        PathBuf::from("/var/run"),
+        PathBuf::from("/tmp"),
+    ];
+    if let Ok(xdg_runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
+        allowed_bases.push(PathBuf::from(xdg_runtime_dir));
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        allowed_bases.push(Path::new(&home).join(".ollama"));
+    }
+
+    for base in &allowed_bases {
+        match std::fs::canonicalize(base) {
+            Ok(base_canonical) => {
+                if canonical.starts_with(&base_canonical) {
+                    log::debug!("Ollama socket path validated: {:?}", canonical);
+                    return Ok(canonical);
+                }
+            }
+            Err(e) => {
+                log::debug!(
+                    "Skipping non-existent Ollama socket allowlist directory '{}': {}",
+                    base.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    log::error!(
+        "SECURITY: Rejected Ollama Unix socket path '{}' - outside allowed directories",
+        canonical.display()
+    );
+    Err(format!(
+        "Security violation: Ollama Unix socket must live under $XDG_RUNTIME_DIR, /run, \
+         /var/run, /tmp, or $HOME/.ollama. Found: '{}'",
+        canonical.display()
+    ))
+}
+
 /// Validates that an Ollama URL is localhost only
 ///
 /// # Security
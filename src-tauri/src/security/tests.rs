@@ -80,6 +80,35 @@ async fn test_file_size_validation_error_message() {
     assert!(err_msg.contains("10 MB"));
 }
 
+#[cfg(test)]
+mod socket_path_tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_socket_under_tmp_is_allowed() {
+        // NamedTempFile defaults to std::env::temp_dir() (/tmp on Linux),
+        // which is on the allowlist.
+        let socket_stand_in = NamedTempFile::new().unwrap();
+        let result = validate_socket_path(socket_stand_in.path().to_str().unwrap());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_nonexistent_socket_path_rejected() {
+        let result = validate_socket_path("/tmp/this-ollama-socket-does-not-exist.sock");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_socket_outside_allowlist_rejected() {
+        // A real file that exists but lives outside every allowed directory.
+        let result = validate_socket_path("/etc/hostname");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Security violation"));
+    }
+}
+
 // Integration tests for path validation would require a running Tauri app.
 // For manual testing:
 // 1. Run the app in dev mode: npm run tauri dev